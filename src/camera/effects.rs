@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+
+use crate::{
+    camera::{DEFAULT_CAMERA_SCALE, PlayerCamera},
+    characters::{
+        health::{DamageEvent, Health},
+        thunwa::Thunwa,
+    },
+};
+
+// Trauma added to the camera whenever Thunwa takes a hit
+const DAMAGE_TRAUMA: f32 = 0.45;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraFocusTarget {
+    Point(Vec2),
+    Entity(Entity),
+}
+
+#[derive(Debug, Clone)]
+pub struct CameraFocus {
+    pub target: CameraFocusTarget,
+    // None keeps the focus until it's cleared
+    pub timer: Option<Timer>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CameraZoom {
+    pub from: f32,
+    pub to: f32,
+    pub timer: Timer,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct CameraEffects {
+    // 0.0 to 1.0, the shake strength is trauma squared
+    pub trauma: f32,
+    pub trauma_decay: f32,
+    pub max_offset: f32,
+    pub max_angle: f32,
+    pub shake_time: f32,
+    pub zoom: Option<CameraZoom>,
+    pub focus: Option<CameraFocus>,
+}
+
+impl Default for CameraEffects {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            trauma_decay: 1.2,
+            max_offset: 12.0,
+            max_angle: 0.03,
+            shake_time: 0.0,
+            zoom: None,
+            focus: None,
+        }
+    }
+}
+
+impl CameraEffects {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    pub fn focus_point(&self, transform_query: &Query<&GlobalTransform>) -> Option<Vec2> {
+        match self.focus.as_ref()?.target {
+            CameraFocusTarget::Point(point) => Some(point),
+            CameraFocusTarget::Entity(entity) => transform_query
+                .get(entity)
+                .ok()
+                .map(|transform| transform.translation().xy()),
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CameraShakeEvent {
+    pub trauma: f32,
+}
+
+// Eases the projection scale, use `DEFAULT_CAMERA_SCALE` to go back to normal
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CameraZoomEvent {
+    pub scale: f32,
+    pub duration: f32,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CameraFocusEvent {
+    // None gives the camera back to its target
+    pub target: Option<CameraFocusTarget>,
+    pub duration: Option<f32>,
+}
+
+impl CameraZoomEvent {
+    pub fn reset(duration: f32) -> Self {
+        Self {
+            scale: DEFAULT_CAMERA_SCALE,
+            duration,
+        }
+    }
+}
+
+pub fn camera_shake_on_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    thunwa_query: Query<&Health, With<Thunwa>>,
+    mut shake_events: EventWriter<CameraShakeEvent>,
+) {
+    for event in damage_events.read() {
+        // Hits that bounce off i-frames don't count
+        if let Ok(health) = thunwa_query.get(event.target)
+            && !health.is_invulnerable()
+            && !health.is_dead()
+        {
+            shake_events.write(CameraShakeEvent {
+                trauma: DAMAGE_TRAUMA,
+            });
+        }
+    }
+}
+
+pub fn handle_camera_effect_events(
+    mut shake_events: EventReader<CameraShakeEvent>,
+    mut zoom_events: EventReader<CameraZoomEvent>,
+    mut focus_events: EventReader<CameraFocusEvent>,
+    mut camera_query: Query<(&mut CameraEffects, &Projection), With<PlayerCamera>>,
+) {
+    let Ok((mut effects, projection)) = camera_query.single_mut() else {
+        return;
+    };
+
+    for event in shake_events.read() {
+        effects.add_trauma(event.trauma);
+    }
+
+    for event in zoom_events.read() {
+        let current_scale = match projection {
+            Projection::Orthographic(orthographic) => orthographic.scale,
+            _ => DEFAULT_CAMERA_SCALE,
+        };
+
+        effects.zoom = Some(CameraZoom {
+            from: current_scale,
+            to: event.scale,
+            timer: Timer::from_seconds(event.duration.max(0.0), TimerMode::Once),
+        });
+    }
+
+    for event in focus_events.read() {
+        effects.focus = event.target.map(|target| CameraFocus {
+            target,
+            timer: event
+                .duration
+                .map(|duration| Timer::from_seconds(duration, TimerMode::Once)),
+        });
+    }
+}
+
+// Runs after the follow system and layers shake and zoom on top of the followed position
+pub fn update_camera_effects(
+    time: Res<Time>,
+    mut camera_query: Query<
+        (&mut CameraEffects, &mut Transform, &mut Projection),
+        With<PlayerCamera>,
+    >,
+) {
+    let Ok((mut effects, mut transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+
+    let delta = time.delta_secs();
+
+    if let Some(focus) = effects.focus.as_mut()
+        && let Some(timer) = focus.timer.as_mut()
+        && timer.tick(time.delta()).finished()
+    {
+        effects.focus = None;
+    }
+
+    if let Some(zoom) = effects.zoom.as_mut() {
+        zoom.timer.tick(time.delta());
+
+        let t = zoom.timer.fraction();
+        let eased = t * t * (3.0 - 2.0 * t);
+        let scale = zoom.from + (zoom.to - zoom.from) * eased;
+
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            orthographic.scale = scale;
+        }
+
+        if zoom.timer.finished() {
+            effects.zoom = None;
+        }
+    }
+
+    if effects.trauma <= 0.0 {
+        transform.rotation = Quat::IDENTITY;
+        return;
+    }
+
+    effects.shake_time += delta;
+    let shake = effects.trauma * effects.trauma;
+    let time = effects.shake_time;
+
+    // Layered sines stand in for noise, each axis at its own frequencies
+    let noise =
+        |seed: f32| ((time * 37.0 + seed).sin() + (time * 23.0 + seed * 2.0).sin() * 0.5) / 1.5;
+
+    transform.translation.x += effects.max_offset * shake * noise(1.0);
+    transform.translation.y += effects.max_offset * shake * noise(7.0);
+    transform.rotation = Quat::from_rotation_z(effects.max_angle * shake * noise(13.0));
+
+    effects.trauma = (effects.trauma - effects.trauma_decay * delta).max(0.0);
+}
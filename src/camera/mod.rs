@@ -1,17 +1,60 @@
 use bevy::{prelude::*, render::camera::ScalingMode};
+use bevy_rapier2d::prelude::Velocity;
+
+use crate::{camera::effects::CameraEffects, terrains::SceneBounds};
+
+pub mod effects;
+pub mod pixel_perfect;
+
+pub const DEFAULT_CAMERA_SCALE: f32 = 0.7;
 
 #[derive(Component)]
 pub struct PlayerCamera;
 
+// The entity the player camera follows
+#[derive(Component)]
+pub struct CameraTarget;
+
+#[derive(Component, Debug, Clone)]
+pub struct CameraFollow {
+    // Higher values catch up faster
+    pub damping: f32,
+    // Half size of the box the target can move in without moving the camera
+    pub deadzone: Vec2,
+    // How far ahead of the target to look in the direction it's moving
+    pub lookahead: f32,
+    pub lookahead_damping: f32,
+    pub current_lookahead: Vec2,
+    // Followed position before any camera effects are layered on top
+    pub position: Vec2,
+    pub snapped: bool,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            damping: 6.0,
+            deadzone: Vec2::new(24.0, 16.0),
+            lookahead: 48.0,
+            lookahead_damping: 2.5,
+            current_lookahead: Vec2::ZERO,
+            position: Vec2::ZERO,
+            snapped: false,
+        }
+    }
+}
+
 pub fn player_camera_setup(mut commands: Commands) {
     commands.spawn((
         PlayerCamera,
+        CameraFollow::default(),
+        CameraEffects::default(),
         Camera2d,
         Projection::from(OrthographicProjection {
             scaling_mode: ScalingMode::FixedVertical {
                 viewport_height: 640.,
             },
-            scale: 0.7,
+            scale: DEFAULT_CAMERA_SCALE,
             ..OrthographicProjection::default_2d()
         }),
         Transform::from_xyz(0.0, 0.0, 1000.),
@@ -27,6 +70,107 @@ pub fn despawn_player_camera(
     }
 }
 
+// What the camera follows, kept apart from the camera's own transform
+type CameraTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, Option<&'static Velocity>),
+    (With<CameraTarget>, Without<PlayerCamera>),
+>;
+
+pub fn update_camera_follow(
+    time: Res<Time>,
+    mut camera_query: Query<
+        (
+            &mut Transform,
+            &mut CameraFollow,
+            Option<&CameraEffects>,
+            &Projection,
+        ),
+        With<PlayerCamera>,
+    >,
+    target_query: CameraTargetQuery,
+    focus_query: Query<&GlobalTransform>,
+    bounds_query: Query<&SceneBounds>,
+) {
+    let (
+        Ok((mut camera_transform, mut follow, effects, projection)),
+        Ok((target_transform, velocity)),
+    ) = (camera_query.single_mut(), target_query.single())
+    else {
+        return;
+    };
+
+    let delta = time.delta_secs();
+    let camera_pos = follow.position;
+    let target_pos = target_transform.translation.xy();
+
+    // Ease the lookahead towards the direction of travel
+    let desired_lookahead = velocity
+        .map(|velocity| velocity.linvel)
+        .filter(|linvel| linvel.length() > 10.0)
+        .map(|linvel| linvel.normalize() * follow.lookahead)
+        .unwrap_or(Vec2::ZERO);
+    let lookahead_blend = 1.0 - (-follow.lookahead_damping * delta).exp();
+    follow.current_lookahead = follow
+        .current_lookahead
+        .lerp(desired_lookahead, lookahead_blend);
+
+    // A point of interest takes over from the target while it's active
+    let focus_point = effects.and_then(|effects| effects.focus_point(&focus_query));
+    let focus = focus_point.unwrap_or(target_pos + follow.current_lookahead);
+
+    let mut goal = if follow.snapped {
+        // Only chase the part of the offset that leaves the deadzone
+        let offset = focus - camera_pos;
+        let mut goal = camera_pos;
+
+        if focus_point.is_some() {
+            goal = focus;
+        } else {
+            if offset.x.abs() > follow.deadzone.x {
+                goal.x = focus.x - follow.deadzone.x * offset.x.signum();
+            }
+            if offset.y.abs() > follow.deadzone.y {
+                goal.y = focus.y - follow.deadzone.y * offset.y.signum();
+            }
+        }
+
+        let blend = 1.0 - (-follow.damping * delta).exp();
+        camera_pos.lerp(goal, blend)
+    } else {
+        // Jump straight to the target the first frame instead of gliding in from the origin
+        follow.snapped = true;
+        target_pos
+    };
+
+    if let (Ok(bounds), Projection::Orthographic(orthographic)) =
+        (bounds_query.single(), projection)
+    {
+        goal = clamp_to_bounds(goal, orthographic.area.half_size(), bounds.rect);
+    }
+
+    follow.position = goal;
+    camera_transform.translation.x = goal.x;
+    camera_transform.translation.y = goal.y;
+}
+
+// Keeps the view inside the map, centering on it when the map is smaller than the view
+fn clamp_to_bounds(position: Vec2, half_view: Vec2, bounds: Rect) -> Vec2 {
+    let clamp_axis = |value: f32, half: f32, min: f32, max: f32| {
+        if max - min <= half * 2.0 {
+            (min + max) / 2.0
+        } else {
+            value.clamp(min + half, max - half)
+        }
+    };
+
+    Vec2::new(
+        clamp_axis(position.x, half_view.x, bounds.min.x, bounds.max.x),
+        clamp_axis(position.y, half_view.y, bounds.min.y, bounds.max.y),
+    )
+}
+
 #[derive(Component)]
 pub struct MainMenuCamera;
 
@@ -42,3 +186,45 @@ pub fn despawn_main_menu_camera(
         commands.entity(entity).despawn();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: Rect = Rect {
+        min: Vec2::new(0.0, 0.0),
+        max: Vec2::new(1000.0, 500.0),
+    };
+
+    #[test]
+    fn clamp_to_bounds_leaves_positions_inside_alone() {
+        let position = Vec2::new(500.0, 250.0);
+
+        assert_eq!(
+            clamp_to_bounds(position, Vec2::new(100.0, 50.0), BOUNDS),
+            position
+        );
+    }
+
+    #[test]
+    fn clamp_to_bounds_keeps_the_view_edge_on_the_map() {
+        let half_view = Vec2::new(100.0, 50.0);
+
+        assert_eq!(
+            clamp_to_bounds(Vec2::new(-40.0, 490.0), half_view, BOUNDS),
+            Vec2::new(100.0, 450.0)
+        );
+        assert_eq!(
+            clamp_to_bounds(Vec2::new(2000.0, 10.0), half_view, BOUNDS),
+            Vec2::new(900.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn clamp_to_bounds_centers_on_maps_smaller_than_the_view() {
+        assert_eq!(
+            clamp_to_bounds(Vec2::new(0.0, 400.0), Vec2::new(600.0, 50.0), BOUNDS),
+            Vec2::new(500.0, 400.0)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::{
+    image::ImageSampler,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+    window::PrimaryWindow,
+};
+
+use crate::{GameOptions, camera::PlayerCamera};
+
+// One canvas pixel per world unit at `DEFAULT_CAMERA_SCALE`, the player camera shows 640 * 0.7 units
+pub const CANVAS_HEIGHT: u32 = 448;
+pub const CANVAS_WIDTH: u32 = CANVAS_HEIGHT * 16 / 9;
+
+// The upscaled canvas lives on its own layer so the world camera never sees it
+const CANVAS_LAYER: usize = 1;
+
+// Present while the world is rendered to the low resolution canvas
+#[derive(Resource)]
+pub struct PixelPerfectCanvas {
+    pub image: Handle<Image>,
+    // Whole number of screen pixels per canvas pixel
+    pub scale: f32,
+}
+
+impl PixelPerfectCanvas {
+    // Maps a window position onto the player camera viewport, None over the letterbox bars
+    pub fn window_to_viewport(&self, window: &Window, position: Vec2) -> Option<Vec2> {
+        let canvas_size = Vec2::new(CANVAS_WIDTH as f32, CANVAS_HEIGHT as f32);
+        let viewport = (position - window.size() / 2.0) / self.scale + canvas_size / 2.0;
+
+        (viewport.cmpge(Vec2::ZERO).all() && viewport.cmplt(canvas_size).all()).then_some(viewport)
+    }
+}
+
+// Draws the canvas sprite to the window, the UI renders on this camera at native resolution
+#[derive(Component)]
+pub struct PixelPerfectCamera;
+
+#[derive(Component)]
+pub struct PixelPerfectCanvasSprite;
+
+// Everything spawned for the canvas, removed together when the mode is switched off
+type PixelPerfectFilter = Or<(With<PixelPerfectCamera>, With<PixelPerfectCanvasSprite>)>;
+
+// Switches between rendering straight to the window and rendering through the canvas,
+// so the option can be changed from the pause menu without restarting the scene
+pub fn sync_pixel_perfect_mode(
+    mut commands: Commands,
+    game_options: Res<GameOptions>,
+    canvas: Option<Res<PixelPerfectCanvas>>,
+    mut images: ResMut<Assets<Image>>,
+    mut player_camera_query: Query<(Entity, &mut Camera), With<PlayerCamera>>,
+    pixel_perfect_query: Query<Entity, PixelPerfectFilter>,
+) {
+    if game_options.pixel_perfect == canvas.is_some() {
+        return;
+    }
+
+    let Ok((player_camera_entity, mut player_camera)) = player_camera_query.single_mut() else {
+        return;
+    };
+
+    if !game_options.pixel_perfect {
+        player_camera.target = RenderTarget::default();
+        player_camera.order = 0;
+        commands
+            .entity(player_camera_entity)
+            .insert(Msaa::default());
+
+        for entity in pixel_perfect_query.iter() {
+            commands.entity(entity).despawn();
+        }
+
+        commands.remove_resource::<PixelPerfectCanvas>();
+        return;
+    }
+
+    let canvas_size = Extent3d {
+        width: CANVAS_WIDTH,
+        height: CANVAS_HEIGHT,
+        ..Default::default()
+    };
+
+    let mut canvas_image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("pixel_perfect_canvas"),
+            size: canvas_size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        // Nearest filtering keeps the upscaled pixels crisp
+        sampler: ImageSampler::nearest(),
+        ..Default::default()
+    };
+    canvas_image.resize(canvas_size);
+    let image = images.add(canvas_image);
+
+    player_camera.target = RenderTarget::Image(image.clone().into());
+    player_camera.order = -1;
+    commands.entity(player_camera_entity).insert(Msaa::Off);
+
+    commands.spawn((
+        PixelPerfectCanvasSprite,
+        Sprite::from_image(image.clone()),
+        RenderLayers::layer(CANVAS_LAYER),
+    ));
+
+    commands.spawn((
+        PixelPerfectCamera,
+        Camera2d,
+        IsDefaultUiCamera,
+        Msaa::Off,
+        RenderLayers::layer(CANVAS_LAYER),
+    ));
+
+    commands.insert_resource(PixelPerfectCanvas { image, scale: 1.0 });
+}
+
+// Picks the largest whole scale that fits the window, the rest is left as black bars
+pub fn fit_pixel_perfect_canvas(
+    canvas: Option<ResMut<PixelPerfectCanvas>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Projection, With<PixelPerfectCamera>>,
+) {
+    let (Some(mut canvas), Ok(window), Ok(mut projection)) =
+        (canvas, window_query.single(), camera_query.single_mut())
+    else {
+        return;
+    };
+
+    let fit = (window.width() / CANVAS_WIDTH as f32).min(window.height() / CANVAS_HEIGHT as f32);
+    let scale = fit.floor().max(1.0);
+
+    if canvas.scale != scale {
+        canvas.scale = scale;
+    }
+
+    if let Projection::Orthographic(orthographic) = &mut *projection
+        && orthographic.scale != 1.0 / scale
+    {
+        orthographic.scale = 1.0 / scale;
+    }
+}
+
+// Rounds the camera to whole world units so sprites don't shimmer as it moves
+pub fn snap_camera_to_pixels(
+    canvas: Option<Res<PixelPerfectCanvas>>,
+    mut camera_query: Query<&mut Transform, With<PlayerCamera>>,
+) {
+    if canvas.is_none() {
+        return;
+    }
+
+    if let Ok(mut transform) = camera_query.single_mut() {
+        transform.translation.x = transform.translation.x.round();
+        transform.translation.y = transform.translation.y.round();
+    }
+}
+
+pub fn despawn_pixel_perfect(
+    mut commands: Commands,
+    pixel_perfect_query: Query<Entity, PixelPerfectFilter>,
+) {
+    for entity in pixel_perfect_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    commands.remove_resource::<PixelPerfectCanvas>();
+}
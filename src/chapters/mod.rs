@@ -0,0 +1,206 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GameState, MainMenuState, PauseOptionsState, PauseState,
+    cutscene::{CutsceneEndedEvent, PlayCutsceneEvent},
+    load_data_file, load_save_file,
+    objectives::{ObjectiveDefinitions, ObjectiveLog},
+    write_save_file,
+};
+
+const CHAPTERS_PATH: &str = "data/chapters.ron";
+const PROGRESS_SAVE: &str = "progress.ron";
+pub const FIRST_CHAPTER: &str = "chapter_1";
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum ChapterUnlock {
+    ChapterCompleted(String),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChapterDefinition {
+    pub id: String,
+    pub title: String,
+    pub scenes: Vec<String>,
+    // Cutscenes played when the chapter starts and once its objectives are done
+    #[serde(default)]
+    pub intro: Option<String>,
+    #[serde(default)]
+    pub outro: Option<String>,
+    #[serde(default)]
+    pub unlock: Vec<ChapterUnlock>,
+}
+
+// Chapters in story order
+#[derive(Resource, Default, Debug)]
+pub struct ChapterDefinitions(pub Vec<ChapterDefinition>);
+
+impl ChapterDefinitions {
+    pub fn get(&self, id: &str) -> Option<&ChapterDefinition> {
+        self.0.iter().find(|chapter| chapter.id == id)
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone)]
+pub struct ChapterProgress {
+    pub completed: Vec<String>,
+    pub last_played: Option<String>,
+}
+
+impl ChapterProgress {
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.iter().any(|completed| completed == id)
+    }
+
+    pub fn is_unlocked(&self, chapter: &ChapterDefinition) -> bool {
+        chapter.unlock.iter().all(|unlock| match unlock {
+            ChapterUnlock::ChapterCompleted(id) => self.is_completed(id),
+        })
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct CurrentChapter {
+    pub id: String,
+    pub scene: usize,
+    // Set once the objectives are done and the outro is playing
+    pub finishing: bool,
+}
+
+impl Default for CurrentChapter {
+    fn default() -> Self {
+        Self {
+            id: FIRST_CHAPTER.to_string(),
+            scene: 0,
+            finishing: false,
+        }
+    }
+}
+
+impl CurrentChapter {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+// Run condition for scene specific systems, e.g. `in_scene("condo_entering")`
+pub fn in_scene(
+    scene: &'static str,
+) -> impl FnMut(Res<CurrentChapter>, Res<ChapterDefinitions>) -> bool + Clone {
+    move |current_chapter, definitions| {
+        definitions
+            .get(&current_chapter.id)
+            .and_then(|chapter| chapter.scenes.get(current_chapter.scene))
+            .is_some_and(|current_scene| current_scene == scene)
+    }
+}
+
+pub fn load_chapters(mut commands: Commands) {
+    let chapters: Vec<ChapterDefinition> = load_data_file(CHAPTERS_PATH).unwrap_or_default();
+
+    commands.insert_resource(ChapterDefinitions(chapters));
+    commands.insert_resource(load_save_file::<ChapterProgress>(PROGRESS_SAVE).unwrap_or_default());
+}
+
+pub fn start_chapter(
+    definitions: Res<ChapterDefinitions>,
+    mut current_chapter: ResMut<CurrentChapter>,
+    mut progress: ResMut<ChapterProgress>,
+    mut objective_log: ResMut<ObjectiveLog>,
+    mut play_events: EventWriter<PlayCutsceneEvent>,
+) {
+    current_chapter.finishing = false;
+    objective_log.chapter = current_chapter.id.clone();
+
+    progress.last_played = Some(current_chapter.id.clone());
+    write_save_file(PROGRESS_SAVE, &*progress);
+
+    if let Some(intro) = definitions
+        .get(&current_chapter.id)
+        .and_then(|chapter| chapter.intro.clone())
+    {
+        play_events.write(PlayCutsceneEvent { cutscene: intro });
+    }
+}
+
+// A chapter is done once every objective in it that isn't optional is
+#[derive(SystemParam)]
+pub struct ChapterObjectives<'w> {
+    definitions: Res<'w, ObjectiveDefinitions>,
+    log: Res<'w, ObjectiveLog>,
+}
+
+impl ChapterObjectives<'_> {
+    fn done(&self, chapter: &str) -> bool {
+        let objectives = self.definitions.chapter(chapter);
+
+        !objectives.is_empty()
+            && objectives
+                .iter()
+                .filter(|objective| !objective.optional)
+                .all(|objective| self.log.is_completed(&objective.id))
+    }
+}
+
+// Leaving the game for the chapter select resets every state the game may be in
+#[derive(SystemParam)]
+pub struct ChapterSelectTransition<'w> {
+    game_state: ResMut<'w, NextState<GameState>>,
+    main_menu_state: ResMut<'w, NextState<MainMenuState>>,
+    pause_state: ResMut<'w, NextState<PauseState>>,
+    pause_options_state: ResMut<'w, NextState<PauseOptionsState>>,
+}
+
+impl ChapterSelectTransition<'_> {
+    fn go(&mut self) {
+        self.game_state.set(GameState::MainMenu);
+        self.main_menu_state.set(MainMenuState::ChapterSelect);
+        self.pause_state.set(PauseState::None);
+        self.pause_options_state.set(PauseOptionsState::None);
+    }
+}
+
+pub fn update_chapter_completion(
+    definitions: Res<ChapterDefinitions>,
+    objectives: ChapterObjectives,
+    mut current_chapter: ResMut<CurrentChapter>,
+    mut progress: ResMut<ChapterProgress>,
+    mut cutscene_ended_events: EventReader<CutsceneEndedEvent>,
+    mut play_events: EventWriter<PlayCutsceneEvent>,
+    mut chapter_select: ChapterSelectTransition,
+) {
+    let Some(chapter) = definitions.get(&current_chapter.id) else {
+        return;
+    };
+
+    let outro_ended = cutscene_ended_events
+        .read()
+        .any(|event| chapter.outro.as_ref() == Some(&event.cutscene));
+
+    if !current_chapter.finishing {
+        if !objectives.done(&chapter.id) {
+            return;
+        }
+
+        current_chapter.finishing = true;
+
+        if let Some(outro) = chapter.outro.clone() {
+            play_events.write(PlayCutsceneEvent { cutscene: outro });
+            return;
+        }
+    } else if !outro_ended {
+        return;
+    }
+
+    if !progress.is_completed(&chapter.id) {
+        progress.completed.push(chapter.id.clone());
+    }
+    write_save_file(PROGRESS_SAVE, &*progress);
+
+    // Back to the chapter select so the next chapter can be picked
+    chapter_select.go();
+}
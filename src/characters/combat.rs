@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+// Linear damping applied while stunned so the knockback slide settles down
+const HIT_STUN_DAMPING: f32 = 10.0;
+
+// Carried by a `DamageEvent`, so anything with `Health` gets pushed, zombies included once a
+// player attack (e.g. the pistol) sends one
+#[derive(Debug, Clone, Copy)]
+pub struct Knockback {
+    pub direction: Vec2,
+    // Velocity in pixels per second given to the target, independent of its mass
+    pub speed: f32,
+    // Seconds during which the target ignores its own movement input
+    pub stun: f32,
+}
+
+impl Knockback {
+    pub fn away_from(
+        attacker_position: Vec2,
+        target_position: Vec2,
+        speed: f32,
+        stun: f32,
+    ) -> Self {
+        Self {
+            direction: (target_position - attacker_position).normalize_or_zero(),
+            speed,
+            stun,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HitStun {
+    pub timer: Timer,
+}
+
+impl HitStun {
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+pub fn apply_knockback(
+    commands: &mut Commands,
+    target: Entity,
+    knockback: Knockback,
+    mass_properties: Option<&ReadMassProperties>,
+) {
+    let mass = mass_properties
+        .map(|properties| properties.get().mass)
+        .filter(|mass| *mass > 0.0)
+        .unwrap_or(1.0);
+
+    commands.entity(target).insert((
+        ExternalImpulse {
+            impulse: knockback.direction * knockback.speed * mass,
+            torque_impulse: 0.0,
+        },
+        Damping {
+            linear_damping: HIT_STUN_DAMPING,
+            angular_damping: 0.0,
+        },
+        HitStun::new(knockback.stun),
+    ));
+}
+
+pub fn update_hit_stun(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut stunned_query: Query<(Entity, &mut HitStun)>,
+) {
+    for (entity, mut hit_stun) in stunned_query.iter_mut() {
+        if hit_stun.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<(HitStun, Damping)>();
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy_light_2d::prelude::*;
+use rand::prelude::*;
+
+use crate::characters::{thunwa::Thunwa, weapon::Weapon};
+
+// Below this charge the flashlight starts to flicker
+const LOW_BATTERY: f32 = 0.2;
+
+#[derive(Component)]
+pub struct Flashlight {
+    pub on: bool,
+    // Remaining charge from 0.0 to 1.0
+    pub battery: f32,
+    pub drain_per_second: f32,
+    pub flicker_timer: Timer,
+    pub flicker: f32,
+}
+
+impl Default for Flashlight {
+    fn default() -> Self {
+        Self {
+            on: true,
+            battery: 1.0,
+            // A fresh battery lasts three minutes
+            drain_per_second: 1.0 / 180.0,
+            flicker_timer: Timer::from_seconds(0.08, TimerMode::Repeating),
+            flicker: 1.0,
+        }
+    }
+}
+
+impl Flashlight {
+    pub fn replace_battery(&mut self) {
+        self.battery = 1.0;
+        self.flicker = 1.0;
+    }
+}
+
+// One of the point lights that together make up the cone in front of Thunwa
+#[derive(Component)]
+pub struct FlashlightBeam {
+    pub distance: f32,
+    pub intensity: f32,
+}
+
+pub fn spawn_flashlight_beams(parent: &mut ChildSpawnerCommands) {
+    let beams = [(20., 36., 1.4), (56., 52., 1.1), (100., 70., 0.8)];
+
+    for (distance, radius, intensity) in beams {
+        parent.spawn((
+            FlashlightBeam {
+                distance,
+                intensity,
+            },
+            PointLight2d {
+                intensity,
+                radius,
+                color: Color::srgb(1.0, 0.96, 0.85),
+                cast_shadows: true,
+                ..Default::default()
+            },
+            Transform::from_xyz(0.0, -distance, 0.0),
+        ));
+    }
+}
+
+pub fn toggle_flashlight(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut flashlight_query: Query<&mut Flashlight, With<Thunwa>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyF) {
+        return;
+    }
+
+    if let Ok(mut flashlight) = flashlight_query.single_mut() {
+        flashlight.on = !flashlight.on;
+    }
+}
+
+pub fn update_flashlight_battery(
+    time: Res<Time>,
+    mut flashlight_query: Query<&mut Flashlight, With<Thunwa>>,
+) {
+    let Ok(mut flashlight) = flashlight_query.single_mut() else {
+        return;
+    };
+
+    if !flashlight.on || flashlight.battery <= 0.0 {
+        return;
+    }
+
+    flashlight.battery =
+        (flashlight.battery - flashlight.drain_per_second * time.delta_secs()).max(0.0);
+
+    if flashlight.battery >= LOW_BATTERY {
+        flashlight.flicker = 1.0;
+        return;
+    }
+
+    if flashlight.flicker_timer.tick(time.delta()).just_finished() {
+        let mut rng = rand::rng();
+        // The weaker the battery, the more often the light cuts out
+        let cut_out_chance = 1.0 - flashlight.battery / LOW_BATTERY;
+
+        flashlight.flicker = if rng.random::<f32>() < cut_out_chance * 0.5 {
+            0.0
+        } else {
+            rng.random_range(0.4..1.0)
+        };
+    }
+}
+
+pub fn orient_flashlight(
+    thunwa_query: Query<(&Thunwa, Option<&Weapon>, &Flashlight)>,
+    mut beam_query: Query<(&FlashlightBeam, &mut Transform, &mut PointLight2d)>,
+) {
+    let Ok((thunwa, weapon, flashlight)) = thunwa_query.single() else {
+        return;
+    };
+
+    // Follow the aim when Thunwa is armed, otherwise the way he's facing
+    let direction = weapon
+        .map(|weapon| weapon.aim_direction)
+        .filter(|direction| *direction != Vec2::ZERO)
+        .unwrap_or_else(|| thunwa.last_direction.xy())
+        .normalize_or(-Vec2::Y);
+
+    let lit = flashlight.on && flashlight.battery > 0.0;
+
+    for (beam, mut transform, mut light) in beam_query.iter_mut() {
+        let offset = direction * beam.distance;
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+
+        light.intensity = if lit {
+            beam.intensity * flashlight.flicker
+        } else {
+            0.0
+        };
+    }
+}
@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::characters::combat::{Knockback, apply_knockback};
+
+#[derive(Component, Debug, Clone)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+    // Seconds of invulnerability granted after every hit that lands
+    pub invulnerability: f32,
+    pub invulnerable_for: f32,
+    // Health regenerated per second while alive
+    pub regeneration: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            invulnerability: 0.0,
+            invulnerable_for: 0.0,
+            regeneration: 0.0,
+        }
+    }
+
+    pub fn with_invulnerability(mut self, seconds: f32) -> Self {
+        self.invulnerability = seconds;
+        self
+    }
+
+    pub fn with_regeneration(mut self, per_second: f32) -> Self {
+        self.regeneration = per_second;
+        self
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_for > 0.0
+    }
+
+    pub fn make_invulnerable(&mut self, seconds: f32) {
+        self.invulnerable_for = self.invulnerable_for.max(seconds);
+    }
+
+    pub fn heal(&mut self, amount: f32) {
+        if self.is_dead() {
+            return;
+        }
+
+        self.current = (self.current + amount).min(self.max);
+    }
+}
+
+// Marker for entities that should be removed from the world once their health runs out
+#[derive(Component)]
+pub struct DespawnOnDeath;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub knockback: Option<Knockback>,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DeathEvent {
+    pub entity: Entity,
+}
+
+pub fn apply_damage(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut death_events: EventWriter<DeathEvent>,
+    mut health_query: Query<(&mut Health, Option<&ReadMassProperties>)>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut health, mass_properties)) = health_query.get_mut(event.target) else {
+            continue;
+        };
+
+        if health.is_dead() || health.is_invulnerable() {
+            continue;
+        }
+
+        health.current = (health.current - event.amount).max(0.0);
+        health.invulnerable_for = health.invulnerability;
+
+        if health.is_dead() {
+            death_events.write(DeathEvent {
+                entity: event.target,
+            });
+        } else if let Some(knockback) = event.knockback {
+            apply_knockback(&mut commands, event.target, knockback, mass_properties);
+        }
+    }
+}
+
+pub fn update_health(time: Res<Time>, mut health_query: Query<&mut Health>) {
+    for mut health in health_query.iter_mut() {
+        // Ticking the invulnerability window alone shouldn't count as a health change
+        if health.invulnerable_for > 0.0 {
+            let health = health.bypass_change_detection();
+            health.invulnerable_for = (health.invulnerable_for - time.delta_secs()).max(0.0);
+        }
+
+        if health.regeneration > 0.0 && !health.is_dead() && health.current < health.max {
+            let amount = health.regeneration * time.delta_secs();
+            health.heal(amount);
+        }
+    }
+}
+
+pub fn despawn_on_death(
+    mut commands: Commands,
+    mut death_events: EventReader<DeathEvent>,
+    despawn_query: Query<(), With<DespawnOnDeath>>,
+) {
+    for event in death_events.read() {
+        if despawn_query.contains(event.entity) {
+            commands.entity(event.entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::characters::combat::HitStun;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_event::<DamageEvent>()
+            .add_event::<DeathEvent>()
+            .add_systems(Update, apply_damage);
+        app
+    }
+
+    fn hit(app: &mut App, target: Entity, amount: f32) {
+        app.world_mut().send_event(DamageEvent {
+            target,
+            amount,
+            knockback: Some(Knockback::away_from(Vec2::ZERO, Vec2::X, 200.0, 0.3)),
+        });
+        app.update();
+    }
+
+    #[test]
+    fn knockback_pushes_any_damaged_body_away() {
+        let mut app = app();
+        // Not Thunwa, e.g. a zombie shot by the pistol
+        let zombie = app
+            .world_mut()
+            .spawn((Health::new(100.0), ReadMassProperties::default()))
+            .id();
+
+        hit(&mut app, zombie, 10.0);
+
+        let entity = app.world().entity(zombie);
+        assert_eq!(entity.get::<Health>().unwrap().current, 90.0);
+        assert!(entity.contains::<HitStun>());
+        // Without a computed mass the speed is used as is
+        assert_eq!(
+            entity.get::<ExternalImpulse>().unwrap().impulse,
+            Vec2::new(200.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn lethal_hits_skip_the_knockback() {
+        let mut app = app();
+        let zombie = app.world_mut().spawn(Health::new(10.0)).id();
+
+        hit(&mut app, zombie, 10.0);
+
+        assert!(!app.world().entity(zombie).contains::<HitStun>());
+        assert_eq!(app.world().resource::<Events<DeathEvent>>().len(), 1);
+    }
+}
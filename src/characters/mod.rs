@@ -1,2 +1,13 @@
+pub mod combat;
+pub mod flashlight;
+pub mod health;
+pub mod sanity;
 pub mod thunwa;
+pub mod weapon;
 pub mod zombie;
+
+// Collision groups for physics
+pub(crate) const ZOMBIE_COLLISION_GROUP: u32 = 0b0001;
+pub(crate) const PLAYER_COLLISION_GROUP: u32 = 0b0010;
+pub(crate) const WALL_COLLISION_GROUP: u32 = 0b0100;
+pub(crate) const PROJECTILE_COLLISION_GROUP: u32 = 0b1000;
@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+use bevy_aseprite_ultra::prelude::*;
+use bevy_kira_audio::prelude::*;
+use bevy_light_2d::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use rand::prelude::*;
+
+use crate::{
+    characters::{
+        combat::HitStun,
+        flashlight::{Flashlight, FlashlightBeam},
+        thunwa::Thunwa,
+        zombie::Zombie,
+    },
+    sounds::MusicChannel,
+};
+
+// Zombies closer than this start to wear Thunwa down
+const ZOMBIE_FEAR_RADIUS: f32 = 160.0;
+const ZOMBIE_FEAR_RATE: f32 = 6.0;
+const DARKNESS_RATE: f32 = 1.5;
+const LIGHT_RECOVERY_RATE: f32 = 3.0;
+// Below this fraction the distortions kick in
+const LOW_SANITY: f32 = 0.4;
+
+#[derive(Resource)]
+pub struct ThunwaSanity {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for ThunwaSanity {
+    fn default() -> Self {
+        ThunwaSanity {
+            current: 100.0,
+            max: 100.0,
+        }
+    }
+}
+
+impl ThunwaSanity {
+    pub fn fraction(&self) -> f32 {
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+
+    // 0.0 while sane, rising to 1.0 as sanity runs out
+    pub fn distortion(&self) -> f32 {
+        (1.0 - self.fraction() / LOW_SANITY).clamp(0.0, 1.0)
+    }
+}
+
+// Something horrible to look at, e.g. the blood stains outside the condo. Only shocks once.
+#[derive(Component)]
+pub struct GoreSpot {
+    pub radius: f32,
+    pub shock: f32,
+    pub witnessed: bool,
+}
+
+impl GoreSpot {
+    pub fn new(radius: f32, shock: f32) -> Self {
+        Self {
+            radius,
+            shock,
+            witnessed: false,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Hallucination {
+    pub lifetime: Timer,
+}
+
+#[derive(Resource)]
+pub struct SanityEffectsTimer {
+    pub audio_timer: Timer,
+    pub hallucination_timer: Timer,
+    pub wobble_time: f32,
+}
+
+impl Default for SanityEffectsTimer {
+    fn default() -> Self {
+        SanityEffectsTimer {
+            audio_timer: Timer::from_seconds(0.25, TimerMode::Repeating),
+            hallucination_timer: Timer::from_seconds(4.0, TimerMode::Repeating),
+            wobble_time: 0.0,
+        }
+    }
+}
+
+pub fn reset_sanity(
+    mut thunwa_sanity: ResMut<ThunwaSanity>,
+    mut effects_timer: ResMut<SanityEffectsTimer>,
+) {
+    *thunwa_sanity = ThunwaSanity::default();
+    *effects_timer = SanityEffectsTimer::default();
+}
+
+pub fn update_sanity(
+    time: Res<Time>,
+    mut thunwa_sanity: ResMut<ThunwaSanity>,
+    thunwa_query: Query<(&Transform, &Flashlight), With<Thunwa>>,
+    zombie_query: Query<&Transform, With<Zombie>>,
+    light_query: Query<(&PointLight2d, &GlobalTransform), Without<FlashlightBeam>>,
+    mut gore_query: Query<(&mut GoreSpot, &GlobalTransform)>,
+) {
+    let Ok((thunwa_transform, flashlight)) = thunwa_query.single() else {
+        return;
+    };
+
+    let thunwa_pos = thunwa_transform.translation.xy();
+    let delta = time.delta_secs();
+    let mut change = 0.0;
+
+    for zombie_transform in zombie_query.iter() {
+        let distance = (zombie_transform.translation.xy() - thunwa_pos).length();
+        if distance < ZOMBIE_FEAR_RADIUS {
+            change -= ZOMBIE_FEAR_RATE * (1.0 - distance / ZOMBIE_FEAR_RADIUS) * delta;
+        }
+    }
+
+    let in_light = light_query.iter().any(|(light, light_transform)| {
+        light.intensity > 0.5
+            && (light_transform.translation().xy() - thunwa_pos).length() < light.radius * 0.8
+    });
+
+    if in_light {
+        change += LIGHT_RECOVERY_RATE * delta;
+    } else if flashlight.on && flashlight.battery > 0.0 {
+        // The flashlight only takes the edge off
+        change -= DARKNESS_RATE * 0.5 * delta;
+    } else {
+        change -= DARKNESS_RATE * delta;
+    }
+
+    for (mut gore_spot, gore_transform) in gore_query.iter_mut() {
+        if gore_spot.witnessed {
+            continue;
+        }
+
+        if (gore_transform.translation().xy() - thunwa_pos).length() < gore_spot.radius {
+            gore_spot.witnessed = true;
+            change -= gore_spot.shock;
+        }
+    }
+
+    if change != 0.0 {
+        thunwa_sanity.current = (thunwa_sanity.current + change).clamp(0.0, thunwa_sanity.max);
+    }
+}
+
+pub fn apply_control_wobble(
+    thunwa_sanity: Res<ThunwaSanity>,
+    time: Res<Time>,
+    mut effects_timer: ResMut<SanityEffectsTimer>,
+    mut thunwa_query: Query<&mut Velocity, (With<Thunwa>, Without<HitStun>)>,
+) {
+    let distortion = thunwa_sanity.distortion();
+    if distortion <= 0.0 {
+        return;
+    }
+
+    effects_timer.wobble_time += time.delta_secs();
+
+    if let Ok(mut velocity) = thunwa_query.single_mut() {
+        // Up to ~20 degrees of drift that slowly sways from side to side
+        let angle = (effects_timer.wobble_time * 2.3).sin() * 0.35 * distortion;
+        velocity.linvel = Vec2::from_angle(angle).rotate(velocity.linvel);
+    }
+}
+
+pub fn apply_audio_distortion(
+    thunwa_sanity: Res<ThunwaSanity>,
+    time: Res<Time>,
+    mut effects_timer: ResMut<SanityEffectsTimer>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+) {
+    if !effects_timer.audio_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let distortion = thunwa_sanity.distortion() as f64;
+    if distortion <= 0.0 {
+        music_channel.set_playback_rate(1.0);
+        return;
+    }
+
+    // Music drags and warbles the further gone Thunwa is
+    let warble = (effects_timer.wobble_time as f64 * 1.7).sin() * 0.05 * distortion;
+    music_channel.set_playback_rate(1.0 - 0.15 * distortion + warble);
+}
+
+pub fn reset_audio_distortion(music_channel: Res<AudioChannel<MusicChannel>>) {
+    music_channel.set_playback_rate(1.0);
+}
+
+pub fn spawn_hallucinations(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    thunwa_sanity: Res<ThunwaSanity>,
+    time: Res<Time>,
+    mut effects_timer: ResMut<SanityEffectsTimer>,
+    thunwa_query: Query<&Transform, With<Thunwa>>,
+) {
+    let distortion = thunwa_sanity.distortion();
+    if distortion <= 0.0 {
+        return;
+    }
+
+    if !effects_timer
+        .hallucination_timer
+        .tick(time.delta())
+        .just_finished()
+    {
+        return;
+    }
+
+    let mut rng = rand::rng();
+    if rng.random::<f32>() > distortion {
+        return;
+    }
+
+    let Ok(thunwa_transform) = thunwa_query.single() else {
+        return;
+    };
+
+    // Just out of reach, at the edge of what Thunwa can see
+    let angle = rng.random_range(0.0..std::f32::consts::TAU);
+    let position = thunwa_transform.translation.xy() + Vec2::from_angle(angle) * 180.0;
+
+    commands.spawn((
+        Hallucination {
+            lifetime: Timer::from_seconds(1.5, TimerMode::Once),
+        },
+        AseAnimation {
+            aseprite: asset_server.load("characters/zombie/zombie_sprite.aseprite"),
+            animation: Animation::default().with_speed(1.0),
+        },
+        Sprite {
+            color: Color::WHITE.with_alpha(0.6),
+            ..Default::default()
+        },
+        Transform::from_xyz(position.x, position.y, 15.0),
+    ));
+}
+
+pub fn update_hallucinations(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut hallucination_query: Query<(Entity, &mut Hallucination, &mut Sprite)>,
+) {
+    for (entity, mut hallucination, mut sprite) in hallucination_query.iter_mut() {
+        if hallucination.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        sprite.color = Color::WHITE.with_alpha(0.6 * hallucination.lifetime.fraction_remaining());
+    }
+}
+
+pub fn despawn_hallucinations(
+    mut commands: Commands,
+    hallucination_query: Query<Entity, With<Hallucination>>,
+) {
+    for entity in hallucination_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
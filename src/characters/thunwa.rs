@@ -1,28 +1,78 @@
 use bevy::prelude::*;
 use bevy_aseprite_ultra::prelude::*;
+use bevy_kira_audio::prelude::{SpatialAudioEmitter, SpatialAudioReceiver};
 use bevy_rapier2d::prelude::*;
 
 use crate::{
-    camera::PlayerCamera,
+    GameState,
+    camera::CameraTarget,
+    chapters::CurrentChapter,
+    characters::{
+        PLAYER_COLLISION_GROUP, WALL_COLLISION_GROUP, ZOMBIE_COLLISION_GROUP,
+        combat::HitStun,
+        flashlight::{Flashlight, spawn_flashlight_beams},
+        health::{DeathEvent, Health},
+        weapon::Weapon,
+    },
+    cutscene::ActiveCutscene,
+    dialogue::ActiveDialogue,
+    sounds::sfx::{Footsteps, SoundEffect},
     terrains::{GRID_SIZE, MAP_SIZE},
 };
 
-// Collision groups for physics
-const PLAYER_COLLISION_GROUP: u32 = 0b0010;
-const WALL_COLLISION_GROUP: u32 = 0b0100;
-const ZOMBIE_COLLISION_GROUP: u32 = 0b0001;
+// Dodge roll tuning
+const DODGE_SPEED: f32 = 420.;
+const DODGE_DURATION: f32 = 0.3;
+const DODGE_COOLDOWN: f32 = 0.8;
+// The roll tags reuse the walk frames until the roll art is drawn, sped up so the whole cycle
+// fits in one roll
+const ROLL_ANIMATION_SPEED: f32 = 4.;
+
+// Sprint tuning
+const SPRINT_MULTIPLIER: f32 = 1.6;
+const EXHAUSTED_MULTIPLIER: f32 = 0.6;
+// Fraction of stamina that has to come back before Thunwa stops being exhausted
+const EXHAUSTION_RECOVERY: f32 = 0.3;
 
 #[derive(Resource)]
-pub struct ThunwaHealth {
+pub struct ThunwaStamina {
     pub current: f32,
     pub max: f32,
+    pub drain_rate: f32,
+    pub regen_rate: f32,
+    pub exhausted: bool,
 }
 
-impl Default for ThunwaHealth {
+impl Default for ThunwaStamina {
     fn default() -> Self {
-        ThunwaHealth {
+        ThunwaStamina {
             current: 100.0,
             max: 100.0,
+            drain_rate: 25.0,
+            regen_rate: 15.0,
+            exhausted: false,
+        }
+    }
+}
+
+impl ThunwaStamina {
+    pub fn fraction(&self) -> f32 {
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+
+    pub fn update(&mut self, sprinting: bool, delta: f32) {
+        if sprinting {
+            self.current = (self.current - self.drain_rate * delta).max(0.0);
+
+            if self.current <= 0.0 {
+                self.exhausted = true;
+            }
+        } else {
+            self.current = (self.current + self.regen_rate * delta).min(self.max);
+
+            if self.exhausted && self.current >= self.max * EXHAUSTION_RECOVERY {
+                self.exhausted = false;
+            }
         }
     }
 }
@@ -33,13 +83,71 @@ pub struct Thunwa {
     pub last_direction: Vec3,
 }
 
+impl Thunwa {
+    // Snaps to whichever axis the direction leans towards the most
+    pub fn face(&mut self, direction: Vec2) {
+        self.last_direction = if direction.x.abs() > direction.y.abs() {
+            Vec3::X * direction.x.signum()
+        } else {
+            Vec3::Y * direction.y.signum()
+        };
+    }
+
+    pub fn walk_tag(&self) -> &'static str {
+        if self.last_direction == Vec3::Y {
+            "walk-back"
+        } else if self.last_direction == -Vec3::X {
+            "walk-left"
+        } else if self.last_direction == Vec3::X {
+            "walk-right"
+        } else {
+            "walk-front"
+        }
+    }
+
+    pub fn idle_tag(&self) -> &'static str {
+        if self.last_direction == Vec3::Y {
+            "idle-back"
+        } else if self.last_direction == -Vec3::X {
+            "idle-left"
+        } else if self.last_direction == Vec3::X {
+            "idle-right"
+        } else {
+            "idle-front"
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct ThunwaCollider;
 
+#[derive(Component)]
+pub struct ThunwaDodge {
+    pub rolling: bool,
+    pub direction: Vec2,
+    pub roll_timer: Timer,
+    pub cooldown_timer: Timer,
+}
+
+impl Default for ThunwaDodge {
+    fn default() -> Self {
+        let mut cooldown_timer = Timer::from_seconds(DODGE_COOLDOWN, TimerMode::Once);
+        // Start off cooldown so Thunwa can roll right away
+        cooldown_timer.tick(cooldown_timer.duration());
+
+        ThunwaDodge {
+            rolling: false,
+            direction: Vec2::ZERO,
+            roll_timer: Timer::from_seconds(DODGE_DURATION, TimerMode::Once),
+            cooldown_timer,
+        }
+    }
+}
+
 pub fn setup_thunwa(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut thunwa_health: ResMut<ThunwaHealth>,
+    mut thunwa_stamina: ResMut<ThunwaStamina>,
 ) {
     let aseprite = asset_server.load("characters/thunwa/thunwa_sprite.aseprite");
 
@@ -49,6 +157,11 @@ pub fn setup_thunwa(
                 speed: 160.,
                 last_direction: Vec3::ZERO,
             },
+            Health::new(100.).with_invulnerability(0.5),
+            ThunwaDodge::default(),
+            CameraTarget,
+            Weapon::pistol(),
+            Flashlight::default(),
             AseAnimation {
                 aseprite,
                 animation: Animation::tag("idle-front").with_speed(1.),
@@ -57,7 +170,14 @@ pub fn setup_thunwa(
             RigidBody::Dynamic,
         ))
         .insert(Velocity::zero())
+        .insert(ReadMassProperties::default())
         .insert(LockedAxes::ROTATION_LOCKED)
+        // Every positional sound is heard from where Thunwa stands
+        .insert((
+            SpatialAudioReceiver,
+            SpatialAudioEmitter { instances: vec![] },
+            Footsteps::new(SoundEffect::ThunwaFootstep, 2),
+        ))
         .insert(Transform::from_xyz(
             -((GRID_SIZE * MAP_SIZE.x as f32) / 2. - (GRID_SIZE * 3.)),
             -(GRID_SIZE * 8.),
@@ -75,9 +195,10 @@ pub fn setup_thunwa(
                     ActiveEvents::COLLISION_EVENTS,
                 ))
                 .insert(Transform::from_xyz(0.0, -16.0, 0.));
-        });
+        })
+        .with_children(spawn_flashlight_beams);
 
-    thunwa_health.current = thunwa_health.max;
+    *thunwa_stamina = ThunwaStamina::default();
 }
 
 pub fn despawn_thunwa(mut commands: Commands, thunwa_query: Query<Entity, With<Thunwa>>) {
@@ -86,66 +207,216 @@ pub fn despawn_thunwa(mut commands: Commands, thunwa_query: Query<Entity, With<T
     }
 }
 
+// Dying sends Thunwa back to the start of the chapter, going through `GameState::Restarting`
+// runs the whole teardown and setup again
+pub fn thunwa_death_handler(
+    mut commands: Commands,
+    mut death_events: EventReader<DeathEvent>,
+    thunwa_query: Query<(), With<Thunwa>>,
+    current_chapter: Res<CurrentChapter>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if death_events
+        .read()
+        .any(|event| thunwa_query.contains(event.entity))
+    {
+        commands.insert_resource(CurrentChapter::new(&current_chapter.id));
+        next_game_state.set(GameState::Restarting);
+    }
+}
+
+pub fn thunwa_dodge(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            &Thunwa,
+            &mut ThunwaDodge,
+            &mut Health,
+            &mut Velocity,
+            &mut AseAnimation,
+        ),
+        Without<HitStun>,
+    >,
+) {
+    if let Ok((thunwa, mut dodge, mut health, mut vel, mut animation)) = query.single_mut() {
+        dodge.cooldown_timer.tick(time.delta());
+
+        if dodge.rolling {
+            if dodge.roll_timer.tick(time.delta()).finished() {
+                dodge.rolling = false;
+                vel.linvel = Vec2::ZERO;
+            } else {
+                vel.linvel = dodge.direction * DODGE_SPEED;
+            }
+            return;
+        }
+
+        if !keyboard_input.just_pressed(KeyCode::Space) || !dodge.cooldown_timer.finished() {
+            return;
+        }
+
+        let (direction, roll_tag) = if thunwa.last_direction == Vec3::Y {
+            (Vec2::Y, "roll-back")
+        } else if thunwa.last_direction == -Vec3::X {
+            (-Vec2::X, "roll-left")
+        } else if thunwa.last_direction == Vec3::X {
+            (Vec2::X, "roll-right")
+        } else {
+            (-Vec2::Y, "roll-front")
+        };
+
+        dodge.rolling = true;
+        dodge.direction = direction;
+        dodge.roll_timer.reset();
+        dodge.cooldown_timer.reset();
+
+        // I-frames last for the whole roll
+        health.make_invulnerable(DODGE_DURATION);
+
+        vel.linvel = direction * DODGE_SPEED;
+        animation.animation = Animation::tag(roll_tag).with_speed(ROLL_ANIMATION_SPEED);
+    }
+}
+
 pub fn thunwa_movement(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Thunwa, &mut Velocity, &mut AseAnimation), With<Thunwa>>,
+    time: Res<Time>,
+    mut thunwa_stamina: ResMut<ThunwaStamina>,
+    active_dialogue: Option<Res<ActiveDialogue>>,
+    active_cutscene: Option<Res<ActiveCutscene>>,
+    mut query: Query<
+        (
+            &mut Thunwa,
+            &mut ThunwaDodge,
+            &mut Velocity,
+            &mut AseAnimation,
+        ),
+        Without<HitStun>,
+    >,
 ) {
-    if let Ok((mut thunwa, mut vel, mut animation)) = query.single_mut() {
+    if let Ok((mut thunwa, mut dodge, mut vel, mut animation)) = query.single_mut() {
+        // `thunwa_dodge` doesn't run during dialogue or cutscenes, so a roll that was going when
+        // one started has to be ended here or it would never stop
+        if (active_cutscene.is_some() || active_dialogue.is_some()) && dodge.rolling {
+            dodge.rolling = false;
+            vel.linvel = Vec2::ZERO;
+        }
+
+        // A playing cutscene walks Thunwa around itself
+        if active_cutscene.is_some() {
+            return;
+        }
+
+        // Thunwa stands still while a conversation is open
+        if active_dialogue.is_some() {
+            vel.linvel = Vec2::ZERO;
+            animation.animation = Animation::tag(thunwa.idle_tag()).with_speed(1.);
+            return;
+        }
+
+        // The roll drives velocity and animation until it ends
+        if dodge.rolling {
+            return;
+        }
+
+        let wants_to_move = keyboard_input.any_pressed([
+            KeyCode::KeyW,
+            KeyCode::KeyA,
+            KeyCode::KeyS,
+            KeyCode::KeyD,
+        ]);
+        let sprinting = wants_to_move
+            && keyboard_input.pressed(KeyCode::ShiftLeft)
+            && !thunwa_stamina.exhausted;
+
+        thunwa_stamina.update(sprinting, time.delta_secs());
+
+        let (speed_multiplier, animation_speed) = if sprinting {
+            (SPRINT_MULTIPLIER, 1.5)
+        } else if thunwa_stamina.exhausted {
+            (EXHAUSTED_MULTIPLIER, 0.7)
+        } else {
+            (1., 1.)
+        };
+
         let mut direction = Vec3::ZERO;
 
         if keyboard_input.pressed(KeyCode::KeyW) {
             direction.y = 1.;
             thunwa.last_direction = Vec3::Y;
-            animation.animation = Animation::tag("walk-back").with_speed(1.);
+            animation.animation = Animation::tag("walk-back").with_speed(animation_speed);
         }
 
         if keyboard_input.pressed(KeyCode::KeyA) {
             direction.x = -1.;
             thunwa.last_direction = -Vec3::X;
-            animation.animation = Animation::tag("walk-left").with_speed(1.);
+            animation.animation = Animation::tag("walk-left").with_speed(animation_speed);
         }
 
         if keyboard_input.pressed(KeyCode::KeyS) {
             direction.y = -1.;
             thunwa.last_direction = -Vec3::Y;
-            animation.animation = Animation::tag("walk-front").with_speed(1.);
+            animation.animation = Animation::tag("walk-front").with_speed(animation_speed);
         }
 
         if keyboard_input.pressed(KeyCode::KeyD) {
             direction.x = 1.;
             thunwa.last_direction = Vec3::X;
-            animation.animation = Animation::tag("walk-right").with_speed(1.);
+            animation.animation = Animation::tag("walk-right").with_speed(animation_speed);
         }
 
         if direction == Vec3::ZERO {
             vel.linvel = Vec2::ZERO;
 
-            let idle_tag = if thunwa.last_direction == Vec3::Y {
-                "idle-back"
-            } else if thunwa.last_direction == -Vec3::X {
-                "idle-left"
-            } else if thunwa.last_direction == Vec3::X {
-                "idle-right"
-            } else {
-                "idle-front"
-            };
-
-            animation.animation = Animation::tag(idle_tag).with_speed(1.);
+            animation.animation = Animation::tag(thunwa.idle_tag()).with_speed(1.);
         } else {
-            let movement = direction.xy().normalize() * thunwa.speed;
+            let movement = direction.xy().normalize() * thunwa.speed * speed_multiplier;
             vel.linvel = movement;
         }
     }
 }
 
-pub fn thunwa_camera_following(
-    mut query: Query<(&mut Transform, Option<&Thunwa>), With<PlayerCamera>>,
-    thunwa_query: Query<&Transform, (With<Thunwa>, Without<PlayerCamera>)>,
-) {
-    if let Ok(thunwa_transform) = thunwa_query.single() {
-        if let Ok((mut camera_transform, _)) = query.single_mut() {
-            camera_transform.translation.x = thunwa_transform.translation.x;
-            camera_transform.translation.y = thunwa_transform.translation.y;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sprinting_drains_and_resting_regenerates() {
+        let mut stamina = ThunwaStamina::default();
+
+        stamina.update(true, 1.0);
+        assert_eq!(stamina.current, 75.0);
+
+        stamina.update(false, 1.0);
+        assert_eq!(stamina.current, 90.0);
+
+        stamina.update(false, 10.0);
+        assert_eq!(stamina.current, stamina.max);
+    }
+
+    #[test]
+    fn running_out_exhausts_until_partly_recovered() {
+        let mut stamina = ThunwaStamina::default();
+
+        stamina.update(true, 10.0);
+        assert_eq!(stamina.current, 0.0);
+        assert!(stamina.exhausted);
+
+        // Just short of the recovery threshold
+        stamina.update(false, 1.9);
+        assert!(stamina.exhausted);
+
+        stamina.update(false, 0.2);
+        assert!(!stamina.exhausted);
+    }
+
+    #[test]
+    fn fraction_stays_between_zero_and_one() {
+        let mut stamina = ThunwaStamina::default();
+        assert_eq!(stamina.fraction(), 1.0);
+
+        stamina.current = -5.0;
+        assert_eq!(stamina.fraction(), 0.0);
     }
 }
@@ -0,0 +1,272 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    camera::{PlayerCamera, pixel_perfect::PixelPerfectCanvas},
+    characters::{
+        PROJECTILE_COLLISION_GROUP, WALL_COLLISION_GROUP, ZOMBIE_COLLISION_GROUP,
+        combat::Knockback,
+        health::{DamageEvent, Health},
+        thunwa::Thunwa,
+        zombie::ZombieCollider,
+    },
+    inventory::Inventory,
+    sounds::sfx::{PlaySoundEvent, SoundEffect},
+};
+
+pub const PISTOL_AMMO_ITEM: &str = "pistol_ammo";
+
+// Dead zone for the gamepad right stick before it takes over aiming from the mouse
+const STICK_AIM_DEAD_ZONE: f32 = 0.2;
+const PROJECTILE_LIFETIME: f32 = 1.5;
+const PROJECTILE_KNOCKBACK_SPEED: f32 = 200.0;
+const PROJECTILE_HIT_STUN: f32 = 0.2;
+
+#[derive(Component)]
+pub struct Weapon {
+    pub ammo_item: &'static str,
+    pub magazine: u32,
+    pub magazine_size: u32,
+    pub damage: f32,
+    pub projectile_speed: f32,
+    pub fire_cooldown: Timer,
+    pub reload_timer: Timer,
+    pub reloading: bool,
+    pub aim_direction: Vec2,
+}
+
+impl Weapon {
+    pub fn pistol() -> Self {
+        let mut fire_cooldown = Timer::from_seconds(0.3, TimerMode::Once);
+        fire_cooldown.tick(fire_cooldown.duration());
+
+        Self {
+            ammo_item: PISTOL_AMMO_ITEM,
+            magazine: 12,
+            magazine_size: 12,
+            damage: 34.0,
+            projectile_speed: 900.0,
+            fire_cooldown,
+            reload_timer: Timer::from_seconds(1.2, TimerMode::Once),
+            reloading: false,
+            aim_direction: -Vec2::Y,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Projectile {
+    pub damage: f32,
+    pub lifetime: Timer,
+}
+
+pub fn update_aim(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<PlayerCamera>>,
+    canvas: Option<Res<PixelPerfectCanvas>>,
+    gamepads: Query<&Gamepad>,
+    mut thunwa_query: Query<(&Transform, &mut Weapon), With<Thunwa>>,
+) {
+    let Ok((thunwa_transform, mut weapon)) = thunwa_query.single_mut() else {
+        return;
+    };
+
+    // The right stick wins whenever it's pushed, otherwise aim at the cursor
+    if let Some(stick) = gamepads
+        .iter()
+        .map(|gamepad| gamepad.right_stick())
+        .find(|stick| stick.length() > STICK_AIM_DEAD_ZONE)
+    {
+        weapon.aim_direction = stick.normalize();
+        return;
+    }
+
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (window_query.single(), camera_query.single())
+    else {
+        return;
+    };
+
+    // With the pixel perfect canvas the cursor has to be mapped through the upscale first
+    if let Some(cursor_world) = window
+        .cursor_position()
+        .and_then(|cursor| match &canvas {
+            Some(canvas) => canvas.window_to_viewport(window, cursor),
+            None => Some(cursor),
+        })
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    {
+        let direction = (cursor_world - thunwa_transform.translation.xy()).normalize_or_zero();
+        if direction != Vec2::ZERO {
+            weapon.aim_direction = direction;
+        }
+    }
+}
+
+pub fn fire_weapon(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut thunwa_query: Query<(&Transform, &mut Weapon), With<Thunwa>>,
+) {
+    let Ok((thunwa_transform, mut weapon)) = thunwa_query.single_mut() else {
+        return;
+    };
+
+    weapon.fire_cooldown.tick(time.delta());
+
+    let trigger_pulled = mouse_input.pressed(MouseButton::Left)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.pressed(GamepadButton::RightTrigger2));
+
+    if !trigger_pulled
+        || weapon.reloading
+        || weapon.magazine == 0
+        || !weapon.fire_cooldown.finished()
+    {
+        return;
+    }
+
+    weapon.magazine -= 1;
+    weapon.fire_cooldown.reset();
+
+    let origin = thunwa_transform.translation.xy() + weapon.aim_direction * 12.0;
+
+    commands.spawn((
+        Projectile {
+            damage: weapon.damage,
+            lifetime: Timer::from_seconds(PROJECTILE_LIFETIME, TimerMode::Once),
+        },
+        Sprite::from_color(Color::srgb(1.0, 0.9, 0.5), Vec2::splat(3.0)),
+        Transform::from_xyz(origin.x, origin.y, 25.0),
+        RigidBody::Dynamic,
+        Collider::ball(1.5),
+        Sensor,
+        Ccd::enabled(),
+        Velocity::linear(weapon.aim_direction * weapon.projectile_speed),
+        CollisionGroups::new(
+            Group::from_bits(PROJECTILE_COLLISION_GROUP).unwrap(),
+            Group::from_bits(ZOMBIE_COLLISION_GROUP | WALL_COLLISION_GROUP).unwrap(),
+        ),
+        ActiveEvents::COLLISION_EVENTS,
+    ));
+}
+
+pub fn reload_weapon(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut inventory: ResMut<Inventory>,
+    mut thunwa_query: Query<&mut Weapon, With<Thunwa>>,
+) {
+    let Ok(mut weapon) = thunwa_query.single_mut() else {
+        return;
+    };
+
+    if weapon.reloading {
+        if weapon.reload_timer.tick(time.delta()).finished() {
+            let needed = weapon.magazine_size - weapon.magazine;
+            let loaded = needed.min(inventory.count(weapon.ammo_item));
+
+            if inventory.take(weapon.ammo_item, loaded) {
+                weapon.magazine += loaded;
+            }
+            weapon.reloading = false;
+        }
+        return;
+    }
+
+    let reload_pressed = keyboard_input.just_pressed(KeyCode::KeyR)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::West));
+
+    if reload_pressed
+        && weapon.magazine < weapon.magazine_size
+        && inventory.count(weapon.ammo_item) > 0
+    {
+        weapon.reloading = true;
+        weapon.reload_timer.reset();
+    }
+}
+
+pub fn projectile_hit_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    projectile_query: Query<(&Projectile, &Transform)>,
+    zombie_collider_query: Query<&ChildOf, With<ZombieCollider>>,
+    target_query: Query<&Transform, With<Health>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(first, second, _) = collision_event else {
+            continue;
+        };
+
+        let (projectile_entity, other) = if projectile_query.contains(*first) {
+            (*first, *second)
+        } else if projectile_query.contains(*second) {
+            (*second, *first)
+        } else {
+            continue;
+        };
+
+        let Ok((projectile, projectile_transform)) = projectile_query.get(projectile_entity) else {
+            continue;
+        };
+
+        let projectile_pos = projectile_transform.translation.xy();
+
+        // The zombie may die from this hit, so the sound plays from where the bullet landed
+        if let Ok(child_of) = zombie_collider_query.get(other)
+            && let Ok(target_transform) = target_query.get(child_of.parent())
+        {
+            sound_events.write(PlaySoundEvent::at_position(
+                SoundEffect::FleshImpact,
+                projectile_pos,
+            ));
+            damage_events.write(DamageEvent {
+                target: child_of.parent(),
+                amount: projectile.damage,
+                knockback: Some(Knockback::away_from(
+                    projectile_pos,
+                    target_transform.translation.xy(),
+                    PROJECTILE_KNOCKBACK_SPEED,
+                    PROJECTILE_HIT_STUN,
+                )),
+            });
+        } else {
+            sound_events.write(PlaySoundEvent::at_position(
+                SoundEffect::BulletImpact,
+                projectile_pos,
+            ));
+        }
+
+        // Projectiles stop at the first thing they hit, wall or zombie
+        commands.entity(projectile_entity).try_despawn();
+    }
+}
+
+pub fn update_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<(Entity, &mut Projectile)>,
+) {
+    for (entity, mut projectile) in projectile_query.iter_mut() {
+        if projectile.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+pub fn despawn_projectiles(
+    mut commands: Commands,
+    projectile_query: Query<Entity, With<Projectile>>,
+) {
+    for entity in projectile_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
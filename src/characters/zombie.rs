@@ -1,23 +1,29 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_aseprite_ultra::prelude::*;
+use bevy_kira_audio::prelude::SpatialAudioEmitter;
 use bevy_rapier2d::prelude::*;
 use pathfinding::prelude::*;
 use rand;
 
 use crate::{
-    characters::thunwa::{Thunwa, ThunwaHealth},
+    characters::{
+        PLAYER_COLLISION_GROUP, PROJECTILE_COLLISION_GROUP, WALL_COLLISION_GROUP,
+        ZOMBIE_COLLISION_GROUP,
+        combat::{HitStun, Knockback},
+        health::{DamageEvent, DespawnOnDeath, Health},
+        thunwa::{Thunwa, ThunwaCollider},
+    },
+    sounds::sfx::{Footsteps, PlaySoundEvent, SoundEffect, ZombieGroans},
     terrains::{DynamicsZOrder, GRID_SIZE, MAP_SIZE},
 };
 
-// Collision groups for physics
-const ZOMBIE_COLLISION_GROUP: u32 = 0b0001;
-const PLAYER_COLLISION_GROUP: u32 = 0b0010;
-const WALL_COLLISION_GROUP: u32 = 0b0100;
+// Knockback given to Thunwa when a zombie lands a hit
+const ZOMBIE_KNOCKBACK_SPEED: f32 = 320.0;
+const ZOMBIE_HIT_STUN: f32 = 0.25;
 
 #[derive(Component)]
 pub struct Zombie {
     pub speed: f32,
-    pub health: f32,
     pub damage: f32,
     pub attack_cooldown: Timer,
     pub path_update_timer: Timer,
@@ -75,7 +81,7 @@ pub fn setup_zombies(mut commands: Commands, asset_server: Res<AssetServer>) {
     spawn_zombie(&mut commands, zombie_sprite, Vec2::new(400.0, -200.0));
 }
 
-fn spawn_zombie(commands: &mut Commands, sprite: Handle<Aseprite>, position: Vec2) {
+pub fn spawn_zombie(commands: &mut Commands, sprite: Handle<Aseprite>, position: Vec2) {
     // Use the first available animation from the sprite
     let animation = Animation::default().with_speed(1.0);
 
@@ -83,7 +89,6 @@ fn spawn_zombie(commands: &mut Commands, sprite: Handle<Aseprite>, position: Vec
         .spawn((
             Zombie {
                 speed: 80.0,
-                health: 100.0,
                 damage: 25.0,
                 attack_cooldown: Timer::from_seconds(1.5, TimerMode::Repeating),
                 path_update_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
@@ -95,6 +100,8 @@ fn spawn_zombie(commands: &mut Commands, sprite: Handle<Aseprite>, position: Vec
                 personal_space_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
                 target_offset: Vec2::ZERO,
             },
+            Health::new(100.0),
+            DespawnOnDeath,
             DynamicsZOrder,
             AseAnimation {
                 aseprite: sprite,
@@ -105,7 +112,13 @@ fn spawn_zombie(commands: &mut Commands, sprite: Handle<Aseprite>, position: Vec
             ZombieTarget,
         ))
         .insert(Velocity::zero())
+        .insert(ReadMassProperties::default())
         .insert(LockedAxes::ROTATION_LOCKED)
+        .insert((
+            SpatialAudioEmitter { instances: vec![] },
+            Footsteps::new(SoundEffect::ZombieFootstep, 3),
+            ZombieGroans::new(),
+        ))
         .insert(Transform::from_xyz(position.x, position.y, 15.0))
         .with_children(|parent| {
             parent
@@ -114,7 +127,12 @@ fn spawn_zombie(commands: &mut Commands, sprite: Handle<Aseprite>, position: Vec
                     Collider::capsule_y(6., 6.), // Smaller collider to prevent getting stuck
                     CollisionGroups::new(
                         Group::from_bits(ZOMBIE_COLLISION_GROUP).unwrap(),
-                        Group::from_bits(PLAYER_COLLISION_GROUP | WALL_COLLISION_GROUP).unwrap(),
+                        Group::from_bits(
+                            PLAYER_COLLISION_GROUP
+                                | WALL_COLLISION_GROUP
+                                | PROJECTILE_COLLISION_GROUP,
+                        )
+                        .unwrap(),
                     ),
                     // Add sensor for collision detection
                     ActiveEvents::COLLISION_EVENTS,
@@ -256,7 +274,10 @@ fn grid_to_world(grid_pos: (i32, i32)) -> Vec2 {
 // Old find_path function removed - replaced by find_alternative_path
 
 pub fn update_zombie_ai(
-    mut zombie_query: Query<(&mut Zombie, &Transform, &mut Velocity, &mut AseAnimation)>,
+    mut zombie_query: Query<
+        (&mut Zombie, &Transform, &mut Velocity, &mut AseAnimation),
+        Without<HitStun>,
+    >,
     zombie_transforms: Query<&Transform, With<Zombie>>,
     thunwa_query: Query<&Transform, (With<Thunwa>, Without<Zombie>)>,
     time: Res<Time>,
@@ -385,18 +406,31 @@ pub fn update_zombie_ai(
     }
 }
 
+// Stunned zombies have to recover before they can attack again
+type AttackingZombieQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static mut Zombie, &'static Transform), Without<HitStun>>;
+
+// The hitboxes that tell a contact between Thunwa and a zombie apart from any other
+#[derive(SystemParam)]
+pub struct AttackColliders<'w, 's> {
+    thunwa: Query<'w, 's, (), With<ThunwaCollider>>,
+    zombies: Query<'w, 's, &'static ChildOf, With<ZombieCollider>>,
+}
+
 pub fn zombie_attack_system(
-    mut zombie_query: Query<(&mut Zombie, &Transform)>,
-    mut thunwa_query: Query<&Transform, (With<Thunwa>, Without<Zombie>)>,
+    mut zombie_query: AttackingZombieQuery,
+    thunwa_query: Query<(Entity, &Transform), With<Thunwa>>,
+    colliders: AttackColliders,
     mut collision_events: EventReader<CollisionEvent>,
-    mut thunwa_health: ResMut<ThunwaHealth>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
     time: Res<Time>,
 ) {
-    if let Ok(thunwa_transform) = thunwa_query.single_mut() {
+    if let Ok((thunwa_entity, thunwa_transform)) = thunwa_query.single() {
         let thunwa_pos = thunwa_transform.translation.xy();
 
         // Update attack cooldowns
-        for (mut zombie, zombie_transform) in zombie_query.iter_mut() {
+        for (entity, mut zombie, zombie_transform) in zombie_query.iter_mut() {
             zombie.attack_cooldown.tick(time.delta());
 
             let zombie_pos = zombie_transform.translation.xy();
@@ -405,43 +439,58 @@ pub fn zombie_attack_system(
             // Check if zombie is close enough to attack
             if distance < 40.0 && zombie.attack_cooldown.finished() {
                 // Deal damage to player
-                thunwa_health.current = (thunwa_health.current - zombie.damage).max(0.0);
-                println!(
-                    "Zombie attacks player for {} damage! Player health: {}/{}",
-                    zombie.damage, thunwa_health.current, thunwa_health.max
-                );
+                damage_events.write(DamageEvent {
+                    target: thunwa_entity,
+                    amount: zombie.damage,
+                    knockback: Some(Knockback::away_from(
+                        zombie_pos,
+                        thunwa_pos,
+                        ZOMBIE_KNOCKBACK_SPEED,
+                        ZOMBIE_HIT_STUN,
+                    )),
+                });
+                sound_events.write(PlaySoundEvent::at_entity(SoundEffect::ZombieAttack, entity));
+                debug!("Zombie attacks player for {} damage", zombie.damage);
                 zombie.attack_cooldown.reset();
-
-                // You could add a damage flash effect here
-
-                // Check if player is dead
-                if thunwa_health.current <= 0.0 {
-                    println!("Player has been defeated by zombies!");
-                    // You could trigger game over state here
-                }
             }
         }
 
         // Alternative: Use collision events for attack detection
         for collision_event in collision_events.read() {
-            if let CollisionEvent::Started(_, _, _) = collision_event {
-                for (mut zombie_entity, _) in zombie_query.iter_mut() {
-                    if zombie_entity.attack_cooldown.finished() {
-                        thunwa_health.current =
-                            (thunwa_health.current - zombie_entity.damage).max(0.0);
-                        println!(
-                            "Zombie hits player via collision! Player health: {}/{}",
-                            thunwa_health.current, thunwa_health.max
-                        );
-                        zombie_entity.attack_cooldown.reset();
-
-                        // Check if player is dead
-                        if thunwa_health.current <= 0.0 {
-                            println!("Player has been defeated by zombies!");
-                            // You could trigger game over state here
-                        }
-                    }
-                }
+            let CollisionEvent::Started(first, second, _) = collision_event else {
+                continue;
+            };
+
+            // Only contacts between Thunwa and a zombie count as an attack
+            let zombie_collider = if colliders.thunwa.contains(*first) {
+                *second
+            } else if colliders.thunwa.contains(*second) {
+                *first
+            } else {
+                continue;
+            };
+
+            let Ok(child_of) = colliders.zombies.get(zombie_collider) else {
+                continue;
+            };
+
+            if let Ok((entity, mut zombie_entity, zombie_transform)) =
+                zombie_query.get_mut(child_of.parent())
+                && zombie_entity.attack_cooldown.finished()
+            {
+                damage_events.write(DamageEvent {
+                    target: thunwa_entity,
+                    amount: zombie_entity.damage,
+                    knockback: Some(Knockback::away_from(
+                        zombie_transform.translation.xy(),
+                        thunwa_pos,
+                        ZOMBIE_KNOCKBACK_SPEED,
+                        ZOMBIE_HIT_STUN,
+                    )),
+                });
+                sound_events.write(PlaySoundEvent::at_entity(SoundEffect::ZombieAttack, entity));
+                debug!("Zombie hits player via collision");
+                zombie_entity.attack_cooldown.reset();
             }
         }
     }
@@ -456,12 +505,3 @@ pub fn update_zombie_animation_direction(mut zombie_query: Query<(&Velocity, &mu
         }
     }
 }
-
-pub fn update_player_health_display(thunwa_health: Res<ThunwaHealth>) {
-    if thunwa_health.current < thunwa_health.max {
-        println!(
-            "Player Health: {}/{}",
-            thunwa_health.current, thunwa_health.max
-        );
-    }
-}
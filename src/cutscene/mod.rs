@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_aseprite_ultra::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use serde::Deserialize;
+
+use crate::{
+    camera::effects::{CameraFocusEvent, CameraFocusTarget, CameraShakeEvent, CameraZoomEvent},
+    characters::{
+        thunwa::Thunwa,
+        zombie::{Zombie, spawn_zombie},
+    },
+    dialogue::{
+        ActiveDialogue, DialogueEndedEvent, DialogueLibrary, StartDialogueEvent, StoryEffect,
+        StoryFlags,
+    },
+    lighting::LightBlackoutEvent,
+    load_data_file,
+    sounds::music_director::{MusicCue, MusicDirector},
+};
+
+const CUTSCENES_PATH: &str = "data/cutscenes.ron";
+// Close enough to a walk target to count as arrived
+const WALK_ARRIVE_DISTANCE: f32 = 4.0;
+// How long the camera takes to settle back once a cutscene is over
+const CAMERA_RELEASE_DURATION: f32 = 0.5;
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum CutsceneStep {
+    // Points the camera somewhere else until `ReleaseCamera`
+    FocusCamera { x: f32, y: f32 },
+    ReleaseCamera,
+    ZoomCamera { scale: f32, duration: f32 },
+    ShakeCamera(f32),
+    // Waits for Thunwa to get there
+    WalkThunwa { x: f32, y: f32 },
+    SpawnZombie { x: f32, y: f32 },
+    // Waits for the conversation to end
+    Dialogue(String),
+    Blackout(f32),
+    ChangeMusic(String),
+    StopMusic,
+    Story(StoryEffect),
+    Wait(f32),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Cutscene {
+    #[serde(default = "default_skippable")]
+    pub skippable: bool,
+    pub steps: Vec<CutsceneStep>,
+}
+
+fn default_skippable() -> bool {
+    true
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct CutsceneLibrary(pub HashMap<String, Cutscene>);
+
+// Only exists while a cutscene is playing
+#[derive(Resource, Debug)]
+pub struct ActiveCutscene {
+    pub cutscene: String,
+    pub step: usize,
+    pub step_started: bool,
+    // Used by the steps that take time, e.g. `Wait` or a walk that gets stuck
+    pub step_timer: Timer,
+    pub skipping: bool,
+}
+
+// Plays a cutscene when Thunwa walks into range
+#[derive(Component, Debug, Clone)]
+pub struct CutsceneTrigger {
+    pub cutscene: String,
+    pub radius: f32,
+    pub triggered: bool,
+}
+
+impl CutsceneTrigger {
+    pub fn new(cutscene: &str, radius: f32) -> Self {
+        Self {
+            cutscene: cutscene.to_string(),
+            radius,
+            triggered: false,
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct PlayCutsceneEvent {
+    pub cutscene: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct CutsceneEndedEvent {
+    pub cutscene: String,
+}
+
+pub fn cutscene_playing(active_cutscene: Option<Res<ActiveCutscene>>) -> bool {
+    active_cutscene.is_some()
+}
+
+pub fn load_cutscenes(mut commands: Commands) {
+    let cutscenes: HashMap<String, Cutscene> = load_data_file(CUTSCENES_PATH).unwrap_or_default();
+
+    commands.insert_resource(CutsceneLibrary(cutscenes));
+}
+
+pub fn stop_cutscene(mut commands: Commands) {
+    commands.remove_resource::<ActiveCutscene>();
+}
+
+pub fn cutscene_trigger_system(
+    mut trigger_query: Query<(&mut CutsceneTrigger, &GlobalTransform)>,
+    thunwa_query: Query<&Transform, With<Thunwa>>,
+    active_cutscene: Option<Res<ActiveCutscene>>,
+    active_dialogue: Option<Res<ActiveDialogue>>,
+    mut play_events: EventWriter<PlayCutsceneEvent>,
+) {
+    if active_cutscene.is_some() || active_dialogue.is_some() {
+        return;
+    }
+
+    let Ok(thunwa_transform) = thunwa_query.single() else {
+        return;
+    };
+
+    for (mut trigger, trigger_transform) in trigger_query.iter_mut() {
+        if trigger.triggered {
+            continue;
+        }
+
+        let distance =
+            (trigger_transform.translation().xy() - thunwa_transform.translation.xy()).length();
+        if distance < trigger.radius {
+            trigger.triggered = true;
+            play_events.write(PlayCutsceneEvent {
+                cutscene: trigger.cutscene.clone(),
+            });
+            return;
+        }
+    }
+}
+
+pub fn play_cutscene_handler(
+    mut commands: Commands,
+    mut play_events: EventReader<PlayCutsceneEvent>,
+    library: Res<CutsceneLibrary>,
+    active_cutscene: Option<Res<ActiveCutscene>>,
+    mut thunwa_query: Query<&mut Velocity, With<Thunwa>>,
+) {
+    let mut playing = active_cutscene.is_some();
+
+    for event in play_events.read() {
+        if playing {
+            continue;
+        }
+
+        if !library.0.contains_key(&event.cutscene) {
+            println!("❌ Unknown cutscene: {}", event.cutscene);
+            continue;
+        }
+
+        // Thunwa stops wherever he was going
+        if let Ok(mut velocity) = thunwa_query.single_mut() {
+            velocity.linvel = Vec2::ZERO;
+        }
+
+        commands.insert_resource(ActiveCutscene {
+            cutscene: event.cutscene.clone(),
+            step: 0,
+            step_started: false,
+            step_timer: Timer::from_seconds(0.0, TimerMode::Once),
+            skipping: false,
+        });
+        playing = true;
+    }
+}
+
+pub fn skip_cutscene(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    library: Res<CutsceneLibrary>,
+    mut active_cutscene: ResMut<ActiveCutscene>,
+) {
+    if active_cutscene.skipping || !keyboard_input.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    let skippable = library
+        .0
+        .get(&active_cutscene.cutscene)
+        .is_some_and(|cutscene| cutscene.skippable);
+
+    if skippable {
+        active_cutscene.skipping = true;
+        commands.remove_resource::<ActiveDialogue>();
+    }
+}
+
+// Zombies hold still while the cutscene has the stage
+pub fn hold_zombies(mut zombie_query: Query<&mut Velocity, With<Zombie>>) {
+    for mut velocity in zombie_query.iter_mut() {
+        velocity.linvel = Vec2::ZERO;
+    }
+}
+
+// The game state that steps change, other than Thunwa
+#[derive(SystemParam)]
+pub struct CutsceneStage<'w> {
+    asset_server: Res<'w, AssetServer>,
+    music_director: ResMut<'w, MusicDirector>,
+    dialogue_library: Res<'w, DialogueLibrary>,
+    story_flags: ResMut<'w, StoryFlags>,
+}
+
+// Steps hand the camera, lights and dialogue their work through events
+#[derive(SystemParam)]
+pub struct CutsceneEvents<'w, 's> {
+    dialogue_ended: EventReader<'w, 's, DialogueEndedEvent>,
+    camera_focus: EventWriter<'w, CameraFocusEvent>,
+    camera_zoom: EventWriter<'w, CameraZoomEvent>,
+    camera_shake: EventWriter<'w, CameraShakeEvent>,
+    blackout: EventWriter<'w, LightBlackoutEvent>,
+    start_dialogue: EventWriter<'w, StartDialogueEvent>,
+    cutscene_ended: EventWriter<'w, CutsceneEndedEvent>,
+}
+
+pub fn run_cutscene(
+    mut commands: Commands,
+    time: Res<Time>,
+    library: Res<CutsceneLibrary>,
+    mut active_cutscene: ResMut<ActiveCutscene>,
+    mut thunwa_query: Query<(
+        &mut Thunwa,
+        &mut Transform,
+        &mut Velocity,
+        &mut AseAnimation,
+    )>,
+    mut stage: CutsceneStage,
+    mut events: CutsceneEvents,
+) {
+    let Some(cutscene) = library.0.get(&active_cutscene.cutscene) else {
+        commands.remove_resource::<ActiveCutscene>();
+        return;
+    };
+
+    let ended_dialogues: Vec<String> = events
+        .dialogue_ended
+        .read()
+        .map(|event| event.dialogue.clone())
+        .collect();
+    let skipping = active_cutscene.skipping;
+
+    // Steps that finish straight away fall through to the next one in the same frame
+    loop {
+        let Some(step) = cutscene.steps.get(active_cutscene.step) else {
+            if let Ok((thunwa, _, mut velocity, mut animation)) = thunwa_query.single_mut() {
+                velocity.linvel = Vec2::ZERO;
+                animation.animation = Animation::tag(thunwa.idle_tag()).with_speed(1.);
+            }
+
+            events.camera_focus.write(CameraFocusEvent {
+                target: None,
+                duration: None,
+            });
+            events
+                .camera_zoom
+                .write(CameraZoomEvent::reset(CAMERA_RELEASE_DURATION));
+            events.cutscene_ended.write(CutsceneEndedEvent {
+                cutscene: active_cutscene.cutscene.clone(),
+            });
+            commands.remove_resource::<ActiveCutscene>();
+            return;
+        };
+
+        let starting = !active_cutscene.step_started;
+        active_cutscene.step_started = true;
+
+        let finished = match step {
+            CutsceneStep::FocusCamera { x, y } => {
+                if starting && !skipping {
+                    events.camera_focus.write(CameraFocusEvent {
+                        target: Some(CameraFocusTarget::Point(Vec2::new(*x, *y))),
+                        duration: None,
+                    });
+                }
+                true
+            }
+            CutsceneStep::ReleaseCamera => {
+                events.camera_focus.write(CameraFocusEvent {
+                    target: None,
+                    duration: None,
+                });
+                true
+            }
+            CutsceneStep::ZoomCamera { scale, duration } => {
+                if !skipping {
+                    events.camera_zoom.write(CameraZoomEvent {
+                        scale: *scale,
+                        duration: *duration,
+                    });
+                }
+                true
+            }
+            CutsceneStep::ShakeCamera(trauma) => {
+                if !skipping {
+                    events
+                        .camera_shake
+                        .write(CameraShakeEvent { trauma: *trauma });
+                }
+                true
+            }
+            CutsceneStep::WalkThunwa { x, y } => {
+                let target = Vec2::new(*x, *y);
+
+                match thunwa_query.single_mut() {
+                    Ok((mut thunwa, mut transform, mut velocity, mut animation)) => {
+                        let offset = target - transform.translation.xy();
+
+                        if starting {
+                            // Give up after twice the time the walk should take
+                            let expected = offset.length() / thunwa.speed.max(1.0);
+                            active_cutscene.step_timer =
+                                Timer::from_seconds(expected * 2.0 + 1.0, TimerMode::Once);
+                        }
+
+                        let timed_out = active_cutscene.step_timer.tick(time.delta()).finished();
+
+                        if skipping {
+                            transform.translation.x = target.x;
+                            transform.translation.y = target.y;
+                        }
+
+                        if skipping || timed_out || offset.length() < WALK_ARRIVE_DISTANCE {
+                            velocity.linvel = Vec2::ZERO;
+                            animation.animation = Animation::tag(thunwa.idle_tag()).with_speed(1.);
+                            true
+                        } else {
+                            let direction = offset.normalize();
+                            thunwa.face(direction);
+                            velocity.linvel = direction * thunwa.speed;
+                            animation.animation = Animation::tag(thunwa.walk_tag()).with_speed(1.);
+                            false
+                        }
+                    }
+                    Err(_) => true,
+                }
+            }
+            CutsceneStep::SpawnZombie { x, y } => {
+                spawn_zombie(
+                    &mut commands,
+                    stage
+                        .asset_server
+                        .load("characters/zombie/zombie_sprite.aseprite"),
+                    Vec2::new(*x, *y),
+                );
+                true
+            }
+            CutsceneStep::Dialogue(dialogue) => {
+                if skipping || !stage.dialogue_library.dialogues.contains_key(dialogue) {
+                    true
+                } else {
+                    if starting {
+                        events.start_dialogue.write(StartDialogueEvent {
+                            dialogue: dialogue.clone(),
+                        });
+                    }
+                    ended_dialogues.contains(dialogue)
+                }
+            }
+            CutsceneStep::Blackout(duration) => {
+                if !skipping {
+                    events.blackout.write(LightBlackoutEvent {
+                        entity: None,
+                        duration: *duration,
+                    });
+                }
+                true
+            }
+            CutsceneStep::ChangeMusic(path) => {
+                stage.music_director.play(MusicCue::Track(path.clone()));
+                true
+            }
+            CutsceneStep::StopMusic => {
+                stage.music_director.stop();
+                true
+            }
+            CutsceneStep::Story(effect) => {
+                stage.story_flags.apply(std::slice::from_ref(effect));
+                true
+            }
+            CutsceneStep::Wait(seconds) => {
+                if starting {
+                    active_cutscene.step_timer = Timer::from_seconds(*seconds, TimerMode::Once);
+                }
+
+                skipping || active_cutscene.step_timer.tick(time.delta()).finished()
+            }
+        };
+
+        if !finished {
+            return;
+        }
+
+        active_cutscene.step += 1;
+        active_cutscene.step_started = false;
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    characters::thunwa::Thunwa, inventory::Inventory, load_data_file, localization::Localization,
+};
+
+const DIALOGUES_PATH: &str = "data/dialogues.ron";
+// Seconds between each revealed character of the typewriter
+const TYPEWRITER_INTERVAL: f32 = 0.03;
+// Guards against fallback chains that loop back on themselves
+const MAX_FALLBACK_DEPTH: usize = 16;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum StoryCondition {
+    Flag(String),
+    NotFlag(String),
+    HasItem(String),
+}
+
+impl StoryCondition {
+    pub fn is_met(&self, story_flags: &StoryFlags, inventory: &Inventory) -> bool {
+        match self {
+            StoryCondition::Flag(flag) => story_flags.is_set(flag),
+            StoryCondition::NotFlag(flag) => !story_flags.is_set(flag),
+            StoryCondition::HasItem(item) => inventory.count(item) > 0,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum StoryEffect {
+    SetFlag(String),
+    ClearFlag(String),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Speaker {
+    pub name: String,
+    #[serde(default)]
+    pub portrait: Option<String>,
+    #[serde(default = "default_portrait_tag")]
+    pub portrait_tag: String,
+}
+
+fn default_portrait_tag() -> String {
+    "idle-front".to_string()
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DialogueChoice {
+    pub text: String,
+    #[serde(default)]
+    pub conditions: Vec<StoryCondition>,
+    #[serde(default)]
+    pub effects: Vec<StoryEffect>,
+    // None ends the conversation
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DialogueNode {
+    pub speaker: String,
+    pub line: String,
+    // When these aren't met the conversation jumps to `fallback` instead
+    #[serde(default)]
+    pub conditions: Vec<StoryCondition>,
+    #[serde(default)]
+    pub fallback: Option<String>,
+    // Applied as soon as the line is shown
+    #[serde(default)]
+    pub effects: Vec<StoryEffect>,
+    #[serde(default)]
+    pub choices: Vec<DialogueChoice>,
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DialogueTree {
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct DialogueFile {
+    speakers: HashMap<String, Speaker>,
+    dialogues: HashMap<String, DialogueTree>,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct DialogueLibrary {
+    pub speakers: HashMap<String, Speaker>,
+    pub dialogues: HashMap<String, DialogueTree>,
+}
+
+impl DialogueLibrary {
+    pub fn node(&self, dialogue: &str, node: &str) -> Option<&DialogueNode> {
+        self.dialogues.get(dialogue)?.nodes.get(node)
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct StoryFlags(pub HashSet<String>);
+
+impl StoryFlags {
+    pub fn is_set(&self, flag: &str) -> bool {
+        self.0.contains(flag)
+    }
+
+    pub fn apply(&mut self, effects: &[StoryEffect]) {
+        for effect in effects {
+            match effect {
+                StoryEffect::SetFlag(flag) => {
+                    self.0.insert(flag.clone());
+                }
+                StoryEffect::ClearFlag(flag) => {
+                    self.0.remove(flag);
+                }
+            }
+        }
+    }
+}
+
+// Only exists while a conversation is open
+#[derive(Resource, Debug)]
+pub struct ActiveDialogue {
+    pub dialogue: String,
+    pub node: String,
+    // How many characters of the line the typewriter has revealed so far
+    pub revealed: usize,
+    pub typewriter_timer: Timer,
+    // Indices into the node's choices that passed their conditions
+    pub choices: Vec<usize>,
+    pub selected: usize,
+}
+
+impl ActiveDialogue {
+    // Lines are translated under `dialogue.<dialogue>.<node>`, the data file holds the English
+    pub fn line<'a>(
+        &self,
+        library: &'a DialogueLibrary,
+        localization: &'a Localization,
+    ) -> Option<&'a str> {
+        library.node(&self.dialogue, &self.node).map(|node| {
+            localization.text_or(
+                &format!("dialogue.{}.{}", self.dialogue, self.node),
+                &node.line,
+            )
+        })
+    }
+
+    pub fn line_length(&self, library: &DialogueLibrary, localization: &Localization) -> usize {
+        self.line(library, localization)
+            .map(|line| line.chars().count())
+            .unwrap_or(0)
+    }
+}
+
+// Starts a conversation when Thunwa walks into range
+#[derive(Component, Debug, Clone)]
+pub struct DialogueTrigger {
+    pub dialogue: String,
+    pub radius: f32,
+    pub triggered: bool,
+}
+
+impl DialogueTrigger {
+    pub fn new(dialogue: &str, radius: f32) -> Self {
+        Self {
+            dialogue: dialogue.to_string(),
+            radius,
+            triggered: false,
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct StartDialogueEvent {
+    pub dialogue: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct DialogueEndedEvent {
+    pub dialogue: String,
+}
+
+pub fn dialogue_open(active_dialogue: Option<Res<ActiveDialogue>>) -> bool {
+    active_dialogue.is_some()
+}
+
+pub fn load_dialogues(mut commands: Commands) {
+    let file: DialogueFile = load_data_file(DIALOGUES_PATH).unwrap_or_default();
+
+    commands.insert_resource(DialogueLibrary {
+        speakers: file.speakers,
+        dialogues: file.dialogues,
+    });
+}
+
+pub fn reset_story_flags(mut story_flags: ResMut<StoryFlags>) {
+    *story_flags = StoryFlags::default();
+}
+
+pub fn close_dialogue(mut commands: Commands) {
+    commands.remove_resource::<ActiveDialogue>();
+}
+
+// Follows fallbacks until a node whose conditions hold, None if the chain runs out
+fn resolve_node(
+    library: &DialogueLibrary,
+    dialogue: &str,
+    node_id: &str,
+    story_flags: &StoryFlags,
+    inventory: &Inventory,
+) -> Option<String> {
+    let mut node_id = node_id.to_string();
+
+    for _ in 0..MAX_FALLBACK_DEPTH {
+        let Some(node) = library.node(dialogue, &node_id) else {
+            println!("❌ Unknown dialogue node: {dialogue}/{node_id}");
+            return None;
+        };
+
+        if node
+            .conditions
+            .iter()
+            .all(|condition| condition.is_met(story_flags, inventory))
+        {
+            return Some(node_id);
+        }
+
+        node_id = node.fallback.clone()?;
+    }
+
+    None
+}
+
+// Moves the conversation to a node, or ends it when there's nowhere left to go
+// The story state that node conditions read and node effects write
+#[derive(SystemParam)]
+pub struct DialogueStory<'w> {
+    story_flags: ResMut<'w, StoryFlags>,
+    inventory: Res<'w, Inventory>,
+}
+
+fn enter_node(
+    commands: &mut Commands,
+    library: &DialogueLibrary,
+    dialogue: &str,
+    node_id: Option<&str>,
+    story: &mut DialogueStory,
+    ended_events: &mut EventWriter<DialogueEndedEvent>,
+) {
+    let (story_flags, inventory) = (&mut story.story_flags, &story.inventory);
+    let resolved = node_id
+        .and_then(|node_id| resolve_node(library, dialogue, node_id, story_flags, inventory));
+
+    let Some(node_id) = resolved else {
+        commands.remove_resource::<ActiveDialogue>();
+        ended_events.write(DialogueEndedEvent {
+            dialogue: dialogue.to_string(),
+        });
+        return;
+    };
+
+    let Some(node) = library.node(dialogue, &node_id) else {
+        return;
+    };
+
+    story_flags.apply(&node.effects);
+
+    let choices = node
+        .choices
+        .iter()
+        .enumerate()
+        .filter(|(_, choice)| {
+            choice
+                .conditions
+                .iter()
+                .all(|condition| condition.is_met(story_flags, inventory))
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    commands.insert_resource(ActiveDialogue {
+        dialogue: dialogue.to_string(),
+        node: node_id,
+        revealed: 0,
+        typewriter_timer: Timer::from_seconds(TYPEWRITER_INTERVAL, TimerMode::Repeating),
+        choices,
+        selected: 0,
+    });
+}
+
+pub fn dialogue_trigger_system(
+    mut trigger_query: Query<(&mut DialogueTrigger, &GlobalTransform)>,
+    thunwa_query: Query<&Transform, With<Thunwa>>,
+    active_dialogue: Option<Res<ActiveDialogue>>,
+    mut start_events: EventWriter<StartDialogueEvent>,
+) {
+    if active_dialogue.is_some() {
+        return;
+    }
+
+    let Ok(thunwa_transform) = thunwa_query.single() else {
+        return;
+    };
+
+    for (mut trigger, trigger_transform) in trigger_query.iter_mut() {
+        if trigger.triggered {
+            continue;
+        }
+
+        let distance =
+            (trigger_transform.translation().xy() - thunwa_transform.translation.xy()).length();
+        if distance < trigger.radius {
+            trigger.triggered = true;
+            start_events.write(StartDialogueEvent {
+                dialogue: trigger.dialogue.clone(),
+            });
+            // One conversation at a time
+            return;
+        }
+    }
+}
+
+pub fn start_dialogue_handler(
+    mut commands: Commands,
+    mut start_events: EventReader<StartDialogueEvent>,
+    library: Res<DialogueLibrary>,
+    mut story: DialogueStory,
+    active_dialogue: Option<Res<ActiveDialogue>>,
+    mut ended_events: EventWriter<DialogueEndedEvent>,
+) {
+    // Anything asked for while a conversation is open is dropped
+    let mut open = active_dialogue.is_some();
+
+    for event in start_events.read() {
+        if open {
+            continue;
+        }
+
+        let Some(tree) = library.dialogues.get(&event.dialogue) else {
+            println!("❌ Unknown dialogue: {}", event.dialogue);
+            continue;
+        };
+
+        enter_node(
+            &mut commands,
+            &library,
+            &event.dialogue,
+            Some(&tree.start),
+            &mut story,
+            &mut ended_events,
+        );
+        open = true;
+    }
+}
+
+pub fn update_typewriter(
+    time: Res<Time>,
+    library: Res<DialogueLibrary>,
+    localization: Res<Localization>,
+    mut active_dialogue: ResMut<ActiveDialogue>,
+) {
+    let length = active_dialogue.line_length(&library, &localization);
+    if active_dialogue.revealed >= length {
+        return;
+    }
+
+    let ticks = active_dialogue
+        .typewriter_timer
+        .tick(time.delta())
+        .times_finished_this_tick() as usize;
+
+    if ticks > 0 {
+        active_dialogue.revealed = (active_dialogue.revealed + ticks).min(length);
+    }
+}
+
+pub fn advance_dialogue(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    library: Res<DialogueLibrary>,
+    localization: Res<Localization>,
+    mut story: DialogueStory,
+    mut active_dialogue: ResMut<ActiveDialogue>,
+    mut ended_events: EventWriter<DialogueEndedEvent>,
+) {
+    let length = active_dialogue.line_length(&library, &localization);
+    let line_done = active_dialogue.revealed >= length;
+
+    if line_done && !active_dialogue.choices.is_empty() {
+        let count = active_dialogue.choices.len();
+
+        if keyboard_input.any_just_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+            active_dialogue.selected = (active_dialogue.selected + count - 1) % count;
+        }
+
+        if keyboard_input.any_just_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+            active_dialogue.selected = (active_dialogue.selected + 1) % count;
+        }
+    }
+
+    if !keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Enter, KeyCode::KeyE]) {
+        return;
+    }
+
+    // The first press skips the typewriter, the next one moves on
+    if !line_done {
+        active_dialogue.revealed = length;
+        return;
+    }
+
+    let Some(node) = library.node(&active_dialogue.dialogue, &active_dialogue.node) else {
+        commands.remove_resource::<ActiveDialogue>();
+        return;
+    };
+
+    let next = match active_dialogue
+        .choices
+        .get(active_dialogue.selected)
+        .and_then(|index| node.choices.get(*index))
+    {
+        Some(choice) => {
+            story.story_flags.apply(&choice.effects);
+            choice.next.as_deref()
+        }
+        None => node.next.as_deref(),
+    };
+
+    enter_node(
+        &mut commands,
+        &library,
+        &active_dialogue.dialogue,
+        next,
+        &mut story,
+        &mut ended_events,
+    );
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    characters::{flashlight::Flashlight, health::Health, thunwa::Thunwa},
+    load_data_file,
+};
+
+pub mod pickup;
+
+pub const INVENTORY_SLOTS: usize = 12;
+const ITEM_DEFINITIONS_PATH: &str = "data/items.ron";
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ItemEffect {
+    Heal(f32),
+    ReplaceBattery,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ItemDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub max_stack: u32,
+    #[serde(default)]
+    pub effect: Option<ItemEffect>,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct ItemDefinitions(pub HashMap<String, ItemDefinition>);
+
+impl ItemDefinitions {
+    pub fn get(&self, id: &str) -> Option<&ItemDefinition> {
+        self.0.get(id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item: String,
+    pub count: u32,
+}
+
+#[derive(Resource, Debug)]
+pub struct Inventory {
+    pub slots: Vec<Option<ItemStack>>,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self {
+            slots: vec![None; INVENTORY_SLOTS],
+        }
+    }
+}
+
+impl Inventory {
+    // Adds items to existing stacks first, then to empty slots. Returns how many didn't fit.
+    pub fn add(&mut self, definitions: &ItemDefinitions, item: &str, count: u32) -> u32 {
+        let Some(definition) = definitions.get(item) else {
+            println!("❌ Unknown item: {item}");
+            return count;
+        };
+
+        let max_stack = definition.max_stack.max(1);
+        let mut remaining = count;
+
+        for stack in self.slots.iter_mut().flatten() {
+            if remaining == 0 {
+                break;
+            }
+
+            if stack.item == item && stack.count < max_stack {
+                let added = remaining.min(max_stack - stack.count);
+                stack.count += added;
+                remaining -= added;
+            }
+        }
+
+        for slot in self.slots.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+
+            if slot.is_none() {
+                let added = remaining.min(max_stack);
+                *slot = Some(ItemStack {
+                    item: item.to_string(),
+                    count: added,
+                });
+                remaining -= added;
+            }
+        }
+
+        remaining
+    }
+
+    pub fn count(&self, item: &str) -> u32 {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|stack| stack.item == item)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    // Removes up to `count` items from a slot, clearing it once empty
+    pub fn remove_from_slot(&mut self, slot: usize, count: u32) -> u32 {
+        let Some(Some(stack)) = self.slots.get_mut(slot) else {
+            return 0;
+        };
+
+        let removed = count.min(stack.count);
+        stack.count -= removed;
+
+        if stack.count == 0 {
+            self.slots[slot] = None;
+        }
+
+        removed
+    }
+
+    // Takes items by id across all slots, only if the whole amount is available
+    pub fn take(&mut self, item: &str, count: u32) -> bool {
+        if self.count(item) < count {
+            return false;
+        }
+
+        let mut remaining = count;
+        for slot in (0..self.slots.len()).rev() {
+            if remaining == 0 {
+                break;
+            }
+
+            if self.slots[slot]
+                .as_ref()
+                .is_some_and(|stack| stack.item == item)
+            {
+                remaining -= self.remove_from_slot(slot, remaining);
+            }
+        }
+
+        true
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct ItemPickedEvent {
+    pub item: String,
+    pub count: u32,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct UseItemEvent {
+    pub slot: usize,
+}
+
+pub fn load_item_definitions(mut commands: Commands) {
+    let definitions: Vec<ItemDefinition> =
+        load_data_file(ITEM_DEFINITIONS_PATH).unwrap_or_default();
+
+    commands.insert_resource(ItemDefinitions(
+        definitions
+            .into_iter()
+            .map(|definition| (definition.id.clone(), definition))
+            .collect(),
+    ));
+}
+
+pub fn reset_inventory(mut inventory: ResMut<Inventory>) {
+    *inventory = Inventory::default();
+}
+
+pub fn use_item_handler(
+    mut use_item_events: EventReader<UseItemEvent>,
+    mut inventory: ResMut<Inventory>,
+    definitions: Res<ItemDefinitions>,
+    mut thunwa_query: Query<(&mut Health, &mut Flashlight), With<Thunwa>>,
+) {
+    for event in use_item_events.read() {
+        let Some(Some(stack)) = inventory.slots.get(event.slot) else {
+            continue;
+        };
+
+        let Some(effect) = definitions
+            .get(&stack.item)
+            .and_then(|definition| definition.effect)
+        else {
+            continue;
+        };
+
+        let Ok((mut health, mut flashlight)) = thunwa_query.single_mut() else {
+            continue;
+        };
+
+        match effect {
+            ItemEffect::Heal(amount) => {
+                if health.is_dead() || health.current >= health.max {
+                    continue; // Don't waste a medkit on full health
+                }
+
+                health.heal(amount);
+            }
+            ItemEffect::ReplaceBattery => {
+                if flashlight.battery >= 1.0 {
+                    continue;
+                }
+
+                flashlight.replace_battery();
+            }
+        }
+
+        inventory.remove_from_slot(event.slot, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definitions() -> ItemDefinitions {
+        let definition = |id: &str, max_stack| ItemDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            max_stack,
+            effect: None,
+        };
+
+        ItemDefinitions(HashMap::from([
+            ("bandage".to_string(), definition("bandage", 5)),
+            ("key".to_string(), definition("key", 1)),
+        ]))
+    }
+
+    #[test]
+    fn add_fills_existing_stacks_before_empty_slots() {
+        let definitions = definitions();
+        let mut inventory = Inventory::default();
+
+        assert_eq!(inventory.add(&definitions, "bandage", 3), 0);
+        assert_eq!(inventory.add(&definitions, "bandage", 4), 0);
+
+        assert_eq!(
+            inventory.slots[0],
+            Some(ItemStack {
+                item: "bandage".to_string(),
+                count: 5,
+            })
+        );
+        assert_eq!(
+            inventory.slots[1],
+            Some(ItemStack {
+                item: "bandage".to_string(),
+                count: 2,
+            })
+        );
+        assert_eq!(inventory.count("bandage"), 7);
+    }
+
+    #[test]
+    fn add_returns_what_does_not_fit() {
+        let definitions = definitions();
+        let mut inventory = Inventory::default();
+
+        assert_eq!(
+            inventory.add(&definitions, "key", INVENTORY_SLOTS as u32 + 2),
+            2
+        );
+        assert_eq!(inventory.count("key"), INVENTORY_SLOTS as u32);
+        assert_eq!(inventory.add(&definitions, "bandage", 1), 1);
+    }
+
+    #[test]
+    fn add_rejects_unknown_items() {
+        let mut inventory = Inventory::default();
+
+        assert_eq!(inventory.add(&definitions(), "rocket", 3), 3);
+        assert!(inventory.slots.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn take_needs_the_whole_amount() {
+        let definitions = definitions();
+        let mut inventory = Inventory::default();
+        inventory.add(&definitions, "bandage", 3);
+
+        assert!(!inventory.take("bandage", 4));
+        assert_eq!(inventory.count("bandage"), 3);
+    }
+
+    #[test]
+    fn take_spans_stacks_and_clears_empty_slots() {
+        let definitions = definitions();
+        let mut inventory = Inventory::default();
+        inventory.add(&definitions, "bandage", 7);
+
+        assert!(inventory.take("bandage", 3));
+        assert_eq!(inventory.count("bandage"), 4);
+        assert_eq!(inventory.slots[1], None);
+
+        assert!(inventory.take("bandage", 4));
+        assert!(inventory.slots.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+
+use crate::{
+    characters::thunwa::Thunwa,
+    inventory::{Inventory, ItemDefinitions, ItemPickedEvent},
+};
+
+const PICKUP_RADIUS: f32 = 24.0;
+
+#[derive(Component, Debug, Clone)]
+pub struct Pickup {
+    pub item: String,
+    pub count: u32,
+}
+
+impl Pickup {
+    pub fn new(item: &str, count: u32) -> Self {
+        Self {
+            item: item.to_string(),
+            count,
+        }
+    }
+}
+
+// Placeholder look until the items get their own sprites
+pub fn pickup_sprite(item: &str) -> Sprite {
+    let color = match item {
+        "medkit" => Color::srgb(0.8, 0.1, 0.1),
+        "painkillers" => Color::srgb(0.9, 0.9, 0.9),
+        "pistol_ammo" => Color::srgb(0.7, 0.55, 0.2),
+        "battery" => Color::srgb(0.2, 0.5, 0.9),
+        _ => Color::srgb(0.9, 0.8, 0.2),
+    };
+
+    Sprite::from_color(color, Vec2::splat(8.0))
+}
+
+pub fn pickup_items(
+    mut commands: Commands,
+    mut inventory: ResMut<Inventory>,
+    definitions: Res<ItemDefinitions>,
+    mut pickup_query: Query<(Entity, &mut Pickup, &GlobalTransform)>,
+    thunwa_query: Query<&Transform, With<Thunwa>>,
+    mut picked_events: EventWriter<ItemPickedEvent>,
+) {
+    let Ok(thunwa_transform) = thunwa_query.single() else {
+        return;
+    };
+
+    // Thunwa's feet are where the collider sits
+    let thunwa_feet = thunwa_transform.translation.xy() - Vec2::new(0.0, 16.0);
+
+    for (entity, mut pickup, pickup_transform) in pickup_query.iter_mut() {
+        let distance = (pickup_transform.translation().xy() - thunwa_feet).length();
+        if distance > PICKUP_RADIUS {
+            continue;
+        }
+
+        // Would never fit in the inventory, so it would be retried (and logged) every frame
+        if definitions.get(&pickup.item).is_none() {
+            println!("❌ Unknown pickup item: {}", pickup.item);
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let leftover = inventory.add(&definitions, &pickup.item, pickup.count);
+        let picked = pickup.count - leftover;
+
+        if picked == 0 {
+            continue; // Inventory is full
+        }
+
+        picked_events.write(ItemPickedEvent {
+            item: pickup.item.clone(),
+            count: picked,
+        });
+
+        if leftover == 0 {
+            commands.entity(entity).despawn();
+        } else {
+            pickup.count = leftover;
+        }
+    }
+}
@@ -1,8 +1,24 @@
-use bevy::prelude::*;
+use std::time::{Duration, Instant};
+
+use bevy::{
+    asset::io::file::FileAssetReader,
+    prelude::*,
+    window::{PresentMode, VideoModeSelection, WindowMode, WindowPosition},
+};
 use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{localization::Language, sounds::VolumeSetting};
 
 pub mod camera;
+pub mod chapters;
 pub mod characters;
+pub mod cutscene;
+pub mod dialogue;
+pub mod inventory;
+pub mod lighting;
+pub mod localization;
+pub mod objectives;
 pub mod sounds;
 pub mod terrains;
 pub mod ui;
@@ -13,6 +29,8 @@ pub enum GameState {
     MainMenu,
     InGame,
     Paused,
+    // Passed through for a frame so the `InGame` teardown and setup run again, e.g. after dying
+    Restarting,
 }
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,6 +39,7 @@ pub enum MainMenuState {
     #[default]
     MainMenu,
     Options,
+    ChapterSelect,
 }
 
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
@@ -37,27 +56,279 @@ pub enum PauseOptionsState {
     None,
     Paused,
     Options,
+    Inventory,
+    QuestLog,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum WindowModeSelection {
+    // Borderless, the desktop keeps its own resolution
     Fullscreen,
+    // Takes the monitor over, needed for frame rates above the desktop's refresh rate
+    ExclusiveFullscreen,
     Windowed,
 }
 
-#[derive(Resource, Clone, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PresentModeSelection {
+    #[default]
+    Vsync,
+    // Triple buffered, no tearing without the vsync input lag
+    Mailbox,
+    Off,
+}
+
+impl PresentModeSelection {
+    pub const ALL: [PresentModeSelection; 3] = [
+        PresentModeSelection::Vsync,
+        PresentModeSelection::Mailbox,
+        PresentModeSelection::Off,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PresentModeSelection::Vsync => "options.vsync_on",
+            PresentModeSelection::Mailbox => "options.vsync_mailbox",
+            PresentModeSelection::Off => "options.vsync_off",
+        }
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        match self {
+            PresentModeSelection::Vsync => PresentMode::AutoVsync,
+            PresentModeSelection::Mailbox => PresentMode::Mailbox,
+            PresentModeSelection::Off => PresentMode::AutoNoVsync,
+        }
+    }
+}
+
+// Windowed sizes, kept inside the window's `resize_constraints` in main.rs
+pub const RESOLUTION_PRESETS: [(u32, u32); 4] =
+    [(1280, 640), (1280, 720), (1600, 900), (1920, 1080)];
+
+// Multiplies the automatic scale that fits the UI to the window
+pub const UI_SCALE_RANGE: (f32, f32) = (0.5, 1.5);
+
+pub const FRAME_LIMIT_PRESETS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+// Saved to `saves/options.ron` whenever something changes, missing fields keep their default
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
 pub struct GameOptions {
     pub window_mode: WindowModeSelection,
+    // Index into the connected monitors, 0 is usually the primary one
+    pub monitor: usize,
+    pub resolution: (u32, u32),
+    pub present_mode: PresentModeSelection,
+    pub frame_limit: Option<u32>,
+    pub master_volume: f64,
     pub music_volume: f64,
+    pub sfx_volume: f64,
+    pub ambience_volume: f64,
+    pub ui_volume: f64,
+    pub voice_volume: f64,
+    pub muted: bool,
+    // Renders the world to a low resolution canvas and upscales it by a whole number
+    pub pixel_perfect: bool,
+    pub language: Language,
+    pub ui_scale: f32,
 }
 
 impl Default for GameOptions {
     fn default() -> Self {
         Self {
             window_mode: WindowModeSelection::Fullscreen,
+            monitor: 0,
+            resolution: RESOLUTION_PRESETS[0],
+            present_mode: PresentModeSelection::Vsync,
+            frame_limit: None,
+            master_volume: 1.0,
             music_volume: 1.0,
+            sfx_volume: 1.0,
+            ambience_volume: 1.0,
+            ui_volume: 1.0,
+            voice_volume: 1.0,
+            muted: false,
+            pixel_perfect: false,
+            language: Language::English,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+impl GameOptions {
+    pub fn display_options(&self) -> DisplayOptions {
+        DisplayOptions {
+            window_mode: self.window_mode,
+            monitor: self.monitor,
+            resolution: self.resolution,
+            present_mode: self.present_mode,
+        }
+    }
+
+    pub fn volume(&self, setting: VolumeSetting) -> f64 {
+        match setting {
+            VolumeSetting::Master => self.master_volume,
+            VolumeSetting::Music => self.music_volume,
+            VolumeSetting::Sfx => self.sfx_volume,
+            VolumeSetting::Ambience => self.ambience_volume,
+            VolumeSetting::Ui => self.ui_volume,
+            VolumeSetting::Voice => self.voice_volume,
+        }
+    }
+
+    pub fn volume_mut(&mut self, setting: VolumeSetting) -> &mut f64 {
+        match setting {
+            VolumeSetting::Master => &mut self.master_volume,
+            VolumeSetting::Music => &mut self.music_volume,
+            VolumeSetting::Sfx => &mut self.sfx_volume,
+            VolumeSetting::Ambience => &mut self.ambience_volume,
+            VolumeSetting::Ui => &mut self.ui_volume,
+            VolumeSetting::Voice => &mut self.voice_volume,
+        }
+    }
+
+    // What a channel actually plays at once master volume and mute are applied
+    pub fn channel_volume(&self, setting: VolumeSetting) -> f64 {
+        if self.muted {
+            return 0.0;
+        }
+
+        match setting {
+            VolumeSetting::Master => self.master_volume,
+            _ => self.master_volume * self.volume(setting),
+        }
+    }
+}
+
+const OPTIONS_SAVE: &str = "options.ron";
+
+pub fn load_game_options() -> GameOptions {
+    load_save_file(OPTIONS_SAVE).unwrap_or_default()
+}
+
+pub fn save_game_options(game_options: Res<GameOptions>) {
+    // Skip the write for the freshly loaded options
+    if game_options.is_changed() && !game_options.is_added() {
+        write_save_file(OPTIONS_SAVE, &*game_options);
+    }
+}
+
+// Everything in `GameOptions` that goes onto the primary window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayOptions {
+    pub window_mode: WindowModeSelection,
+    pub monitor: usize,
+    pub resolution: (u32, u32),
+    pub present_mode: PresentModeSelection,
+}
+
+// Only touches the window when a display option actually changed, so changing the volume
+// doesn't undo a window that was resized by hand. The first run applies the saved options
+pub fn apply_display_options(
+    game_options: Res<GameOptions>,
+    mut windows_query: Query<&mut Window>,
+    mut applied: Local<Option<DisplayOptions>>,
+) {
+    let display_options = game_options.display_options();
+    if *applied == Some(display_options) {
+        return;
+    }
+
+    let Ok(mut window) = windows_query.single_mut() else {
+        return;
+    };
+
+    let monitor = MonitorSelection::Index(display_options.monitor);
+
+    window.mode = match display_options.window_mode {
+        WindowModeSelection::Fullscreen => WindowMode::BorderlessFullscreen(monitor),
+        WindowModeSelection::ExclusiveFullscreen => {
+            WindowMode::Fullscreen(monitor, VideoModeSelection::Current)
+        }
+        WindowModeSelection::Windowed => WindowMode::Windowed,
+    };
+
+    if display_options.window_mode == WindowModeSelection::Windowed {
+        let (width, height) = display_options.resolution;
+        window.resolution.set(width as f32, height as f32);
+        window.position = WindowPosition::Centered(monitor);
+    }
+
+    window.present_mode = display_options.present_mode.present_mode();
+    *applied = Some(display_options);
+}
+
+// Sleeps off whatever is left of the frame, runs last so the whole frame is measured
+pub fn limit_frame_rate(game_options: Res<GameOptions>, mut last_frame: Local<Option<Instant>>) {
+    if let (Some(frame_limit), Some(last_frame)) = (game_options.frame_limit, *last_frame) {
+        let frame_time = Duration::from_secs_f64(1.0 / frame_limit as f64);
+        let elapsed = last_frame.elapsed();
+
+        if elapsed < frame_time {
+            std::thread::sleep(frame_time - elapsed);
         }
     }
+
+    *last_frame = Some(Instant::now());
+}
+
+// Reads a RON data file from the assets folder, e.g. `load_data_file("data/items.ron")`. The
+// folder is found the same way the asset server finds it, so it doesn't depend on the working
+// directory the game was started from.
+pub fn load_data_file<T: DeserializeOwned>(path: &str) -> Option<T> {
+    let full_path = FileAssetReader::get_base_path().join("assets").join(path);
+
+    let contents = match std::fs::read_to_string(&full_path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            println!("❌ Failed to read {}: {error}", full_path.display());
+            return None;
+        }
+    };
+
+    match ron::from_str(&contents) {
+        Ok(data) => Some(data),
+        Err(error) => {
+            println!("❌ Failed to parse {}: {error}", full_path.display());
+            None
+        }
+    }
+}
+
+// Player saves live next to the game rather than in the assets folder
+const SAVE_DIRECTORY: &str = "saves";
+
+// Reads a RON save file, a missing file just means nothing has been saved yet
+pub fn load_save_file<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let full_path = format!("{SAVE_DIRECTORY}/{name}");
+    let contents = std::fs::read_to_string(&full_path).ok()?;
+
+    match ron::from_str(&contents) {
+        Ok(data) => Some(data),
+        Err(error) => {
+            println!("❌ Failed to parse {full_path}: {error}");
+            None
+        }
+    }
+}
+
+pub fn write_save_file<T: Serialize>(name: &str, data: &T) {
+    let full_path = format!("{SAVE_DIRECTORY}/{name}");
+
+    let contents = match ron::ser::to_string_pretty(data, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            println!("❌ Failed to serialize {full_path}: {error}");
+            return;
+        }
+    };
+
+    if let Err(error) =
+        std::fs::create_dir_all(SAVE_DIRECTORY).and_then(|_| std::fs::write(&full_path, contents))
+    {
+        println!("❌ Failed to write {full_path}: {error}");
+    }
 }
 
 pub fn global_bevy_rapier_config(mut rapier_config: Query<&mut RapierConfiguration>) {
@@ -66,6 +337,10 @@ pub fn global_bevy_rapier_config(mut rapier_config: Query<&mut RapierConfigurati
     }
 }
 
+pub fn finish_restart(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::InGame);
+}
+
 pub fn pause_physics_system(
     pause_state: Res<State<PauseState>>,
     mut rapier_config: Query<&mut RapierConfiguration>,
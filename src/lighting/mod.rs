@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use bevy_light_2d::prelude::*;
+use rand::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlickerPattern {
+    // Holds the maximum intensity, only blackouts affect it
+    Steady,
+    // Picks a new intensity in range every step
+    Random,
+    // Loops through fixed steps (0.0 = dark, 1.0 = max intensity) for scripted scares
+    Sequence(Vec<f32>),
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct LightFlicker {
+    pub pattern: FlickerPattern,
+    pub min_intensity: f32,
+    pub max_intensity: f32,
+    // Chance for a random step to go fully dark
+    pub dark_chance: f32,
+    pub timer: Timer,
+    pub step: usize,
+    pub blackout: Option<Timer>,
+}
+
+impl LightFlicker {
+    pub fn steady(intensity: f32) -> Self {
+        Self {
+            pattern: FlickerPattern::Steady,
+            min_intensity: intensity,
+            max_intensity: intensity,
+            dark_chance: 0.0,
+            timer: Timer::from_seconds(0.1, TimerMode::Repeating),
+            step: 0,
+            blackout: None,
+        }
+    }
+
+    pub fn random(min_intensity: f32, max_intensity: f32, interval: f32) -> Self {
+        Self {
+            pattern: FlickerPattern::Random,
+            min_intensity,
+            max_intensity,
+            timer: Timer::from_seconds(interval, TimerMode::Repeating),
+            ..Self::steady(max_intensity)
+        }
+    }
+
+    pub fn sequence(steps: Vec<f32>, max_intensity: f32, interval: f32) -> Self {
+        Self {
+            pattern: FlickerPattern::Sequence(steps),
+            min_intensity: 0.0,
+            max_intensity,
+            timer: Timer::from_seconds(interval, TimerMode::Repeating),
+            ..Self::steady(max_intensity)
+        }
+    }
+
+    pub fn with_dark_chance(mut self, dark_chance: f32) -> Self {
+        self.dark_chance = dark_chance;
+        self
+    }
+
+    pub fn blackout(&mut self, seconds: f32) {
+        self.blackout = Some(Timer::from_seconds(seconds, TimerMode::Once));
+    }
+
+    fn next_intensity(&mut self) -> f32 {
+        match &self.pattern {
+            FlickerPattern::Steady => self.max_intensity,
+            FlickerPattern::Random => {
+                let mut rng = rand::rng();
+
+                if self.dark_chance > 0.0 && rng.random::<f32>() < self.dark_chance {
+                    0.0
+                } else if self.min_intensity < self.max_intensity {
+                    rng.random_range(self.min_intensity..self.max_intensity)
+                } else {
+                    self.max_intensity
+                }
+            }
+            FlickerPattern::Sequence(steps) => {
+                if steps.is_empty() {
+                    return self.max_intensity;
+                }
+
+                let value = steps[self.step % steps.len()];
+                self.step = (self.step + 1) % steps.len();
+
+                value.clamp(0.0, 1.0) * self.max_intensity
+            }
+        }
+    }
+}
+
+// Kills every flickering light (or a single one) for a while, e.g. during a scripted scare
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LightBlackoutEvent {
+    pub entity: Option<Entity>,
+    pub duration: f32,
+}
+
+pub fn handle_light_blackout(
+    mut blackout_events: EventReader<LightBlackoutEvent>,
+    mut flicker_query: Query<(Entity, &mut LightFlicker)>,
+) {
+    for event in blackout_events.read() {
+        for (entity, mut flicker) in flicker_query.iter_mut() {
+            if event.entity.is_none_or(|target| target == entity) {
+                flicker.blackout(event.duration);
+            }
+        }
+    }
+}
+
+pub fn update_light_flicker(
+    time: Res<Time>,
+    mut flicker_query: Query<(&mut LightFlicker, &mut PointLight2d)>,
+) {
+    for (mut flicker, mut light) in flicker_query.iter_mut() {
+        if let Some(blackout) = flicker.blackout.as_mut() {
+            if !blackout.tick(time.delta()).finished() {
+                light.intensity = 0.0;
+                continue;
+            }
+
+            flicker.blackout = None;
+            light.intensity = flicker.next_intensity();
+        }
+
+        if flicker.timer.tick(time.delta()).just_finished() {
+            light.intensity = flicker.next_intensity();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_loops_through_its_steps() {
+        let mut flicker = LightFlicker::sequence(vec![1.0, 0.0, 0.5], 2.0, 0.1);
+
+        let intensities: Vec<f32> = (0..5).map(|_| flicker.next_intensity()).collect();
+
+        assert_eq!(intensities, vec![2.0, 0.0, 1.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn sequence_clamps_steps_to_max_intensity() {
+        let mut flicker = LightFlicker::sequence(vec![3.0, -1.0], 2.0, 0.1);
+
+        assert_eq!(flicker.next_intensity(), 2.0);
+        assert_eq!(flicker.next_intensity(), 0.0);
+    }
+
+    #[test]
+    fn empty_sequence_holds_max_intensity() {
+        let mut flicker = LightFlicker::sequence(vec![], 2.0, 0.1);
+
+        assert_eq!(flicker.next_intensity(), 2.0);
+        assert_eq!(flicker.next_intensity(), 2.0);
+    }
+
+    #[test]
+    fn random_stays_in_range() {
+        let mut flicker = LightFlicker::random(0.5, 1.5, 0.1);
+
+        for _ in 0..100 {
+            let intensity = flicker.next_intensity();
+            assert!((0.5..1.5).contains(&intensity));
+        }
+    }
+
+    #[test]
+    fn random_with_certain_dark_chance_stays_dark() {
+        let mut flicker = LightFlicker::random(0.5, 1.5, 0.1).with_dark_chance(1.0);
+
+        assert_eq!(flicker.next_intensity(), 0.0);
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{GameOptions, load_data_file};
+
+const LOCALES_PATH: &str = "data/locales";
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    Thai,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Thai];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Thai => "th",
+        }
+    }
+}
+
+// One file per language under assets/data/locales, e.g. `th.ron`
+#[derive(Deserialize, Debug, Default)]
+struct StringTable {
+    // The pixeloid fonts only cover latin glyphs, so a language can bring its own
+    #[serde(default)]
+    font: Option<String>,
+    #[serde(default)]
+    font_bold: Option<String>,
+    strings: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+struct LanguageFonts {
+    regular: Handle<Font>,
+    bold: Handle<Font>,
+}
+
+#[derive(Resource, Debug)]
+pub struct Localization {
+    pub language: Language,
+    strings: HashMap<Language, HashMap<String, String>>,
+    fonts: HashMap<Language, LanguageFonts>,
+    default_fonts: LanguageFonts,
+    // Languages whose own font failed to load, their text would only show up as boxes
+    missing_fonts: HashSet<Language>,
+}
+
+impl FromWorld for Localization {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let default_fonts = LanguageFonts {
+            regular: asset_server.load("ui/fonts/pixeloid_mono.ttf"),
+            bold: asset_server.load("ui/fonts/pixeloid_mono_bold.ttf"),
+        };
+
+        let mut strings = HashMap::new();
+        let mut fonts = HashMap::new();
+
+        for language in Language::ALL {
+            let table: StringTable =
+                load_data_file(&format!("{LOCALES_PATH}/{}.ron", language.code()))
+                    .unwrap_or_default();
+
+            // Languages without their own font use the pixeloid ones
+            let load_font = |path: Option<String>, fallback: &Handle<Font>| {
+                path.map_or_else(|| fallback.clone(), |path| asset_server.load(path))
+            };
+
+            fonts.insert(
+                language,
+                LanguageFonts {
+                    regular: load_font(table.font, &default_fonts.regular),
+                    bold: load_font(table.font_bold, &default_fonts.bold),
+                },
+            );
+            strings.insert(language, table.strings);
+        }
+
+        Self {
+            language: Language::default(),
+            strings,
+            fonts,
+            default_fonts,
+            missing_fonts: HashSet::new(),
+        }
+    }
+}
+
+impl Localization {
+    // Looks in the current language first and then in English, a language without its font
+    // goes straight to English
+    pub fn get(&self, key: &str) -> Option<&str> {
+        [self.language, Language::English]
+            .iter()
+            .filter(|language| !self.missing_fonts.contains(language))
+            .find_map(|language| self.strings.get(language)?.get(key))
+            .map(String::as_str)
+    }
+
+    // Missing keys show up as the key itself so they are easy to spot
+    pub fn text(&self, key: &str) -> String {
+        self.get(key).unwrap_or(key).to_string()
+    }
+
+    // For data files that carry their own English text, e.g. item names
+    pub fn text_or<'a>(&'a self, key: &str, fallback: &'a str) -> &'a str {
+        self.get(key).unwrap_or(fallback)
+    }
+
+    // Fills `{name}` placeholders, e.g. `format("hud.ammo", &[("reserve", "12")])`
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.text(key), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+    }
+
+    pub fn font(&self) -> Handle<Font> {
+        self.fonts
+            .get(&self.language)
+            .map(|fonts| fonts.regular.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn font_bold(&self) -> Handle<Font> {
+        self.fonts
+            .get(&self.language)
+            .map(|fonts| fonts.bold.clone())
+            .unwrap_or_default()
+    }
+}
+
+// Text that is looked up from the string tables and refreshed when the language changes
+#[derive(Component, Debug, Clone)]
+pub struct LocalizedText(pub &'static str);
+
+pub fn sync_language(game_options: Res<GameOptions>, mut localization: ResMut<Localization>) {
+    if game_options.is_changed() && localization.language != game_options.language {
+        localization.language = game_options.language;
+    }
+}
+
+pub fn update_localized_text(
+    localization: Res<Localization>,
+    mut text_query: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    for (localized_text, mut text) in text_query.iter_mut() {
+        if localization.is_changed() || localized_text.is_added() {
+            text.0 = localization.text(localized_text.0);
+        }
+    }
+}
+
+// Swaps every UI font over to the current language's, regular and bold stay apart
+pub fn update_localized_fonts(
+    localization: Res<Localization>,
+    mut text_font_query: Query<&mut TextFont>,
+) {
+    if !localization.is_changed() {
+        return;
+    }
+
+    let (font, font_bold) = (localization.font(), localization.font_bold());
+
+    for mut text_font in text_font_query.iter_mut() {
+        let is_bold = localization
+            .fonts
+            .values()
+            .any(|fonts| fonts.bold == text_font.font);
+        let is_regular = localization
+            .fonts
+            .values()
+            .any(|fonts| fonts.regular == text_font.font);
+
+        if is_bold && text_font.font != font_bold {
+            text_font.font = font_bold.clone();
+        } else if is_regular && text_font.font != font {
+            text_font.font = font.clone();
+        }
+    }
+}
+
+// A language font that fails to load is swapped for the pixeloid one and the language's text for
+// English, since pixeloid has no glyphs for e.g. Thai
+pub fn fall_back_from_failed_fonts(
+    mut failed_events: EventReader<AssetLoadFailedEvent<Font>>,
+    mut localization: ResMut<Localization>,
+    mut text_font_query: Query<&mut TextFont>,
+) {
+    for event in failed_events.read() {
+        println!(
+            "❌ Failed to load font {}, falling back to pixeloid",
+            event.path
+        );
+
+        let default_fonts = localization.default_fonts.clone();
+        let mut fallback = None;
+        let mut missing = Vec::new();
+
+        for (language, fonts) in localization.fonts.iter_mut() {
+            if fonts.regular.id() == event.id {
+                fonts.regular = default_fonts.regular.clone();
+                fallback = Some(default_fonts.regular.clone());
+                missing.push(*language);
+            }
+            if fonts.bold.id() == event.id {
+                fonts.bold = default_fonts.bold.clone();
+                fallback = Some(default_fonts.bold.clone());
+                missing.push(*language);
+            }
+        }
+
+        localization.missing_fonts.extend(missing);
+
+        let Some(fallback) = fallback else {
+            continue;
+        };
+
+        for mut text_font in text_font_query.iter_mut() {
+            if text_font.font.id() == event.id {
+                text_font.font = fallback.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn localization(language: Language) -> Localization {
+        let table = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect()
+        };
+
+        Localization {
+            language,
+            strings: HashMap::from([
+                (
+                    Language::English,
+                    table(&[
+                        ("menu.start", "Start"),
+                        ("hud.ammo", "{loaded} / {reserve}"),
+                        ("menu.quit", "Quit"),
+                    ]),
+                ),
+                (
+                    Language::Thai,
+                    table(&[
+                        ("menu.start", "เริ่ม"),
+                        ("hud.ammo", "{loaded} / {reserve} นัด"),
+                    ]),
+                ),
+            ]),
+            fonts: HashMap::new(),
+            default_fonts: LanguageFonts {
+                regular: Handle::default(),
+                bold: Handle::default(),
+            },
+            missing_fonts: HashSet::new(),
+        }
+    }
+
+    #[test]
+    fn text_falls_back_to_english_then_the_key() {
+        let localization = localization(Language::Thai);
+
+        assert_eq!(localization.text("menu.start"), "เริ่ม");
+        assert_eq!(localization.text("menu.quit"), "Quit");
+        assert_eq!(localization.text("menu.missing"), "menu.missing");
+    }
+
+    #[test]
+    fn text_or_uses_the_fallback_for_missing_keys() {
+        let localization = localization(Language::Thai);
+
+        assert_eq!(localization.text_or("menu.start", "Start"), "เริ่ม");
+        assert_eq!(
+            localization.text_or("item.bandage.name", "Bandage"),
+            "Bandage"
+        );
+    }
+
+    #[test]
+    fn format_fills_every_placeholder() {
+        let localization = localization(Language::English);
+
+        assert_eq!(
+            localization.format("hud.ammo", &[("loaded", "6"), ("reserve", "12")]),
+            "6 / 12"
+        );
+        // Unused arguments are ignored and unfilled placeholders are left as they are
+        assert_eq!(
+            localization.format("hud.ammo", &[("reserve", "12"), ("spare", "1")]),
+            "{loaded} / 12"
+        );
+    }
+
+    #[test]
+    fn languages_without_their_font_show_english() {
+        let mut localization = localization(Language::Thai);
+        localization.missing_fonts.insert(Language::Thai);
+
+        assert_eq!(localization.text("menu.start"), "Start");
+        assert_eq!(
+            localization.format("hud.ammo", &[("loaded", "6"), ("reserve", "12")]),
+            "6 / 12"
+        );
+    }
+}
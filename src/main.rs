@@ -2,17 +2,42 @@ use bevy::{prelude::*, window::WindowMode};
 use bevy_aseprite_ultra::prelude::*;
 use bevy_ecs_tilemap::TilemapPlugin;
 use bevy_inspector_egui::bevy_egui::EguiPlugin;
-use bevy_kira_audio::prelude::*;
+use bevy_kira_audio::{AudioSystemSet, prelude::*};
 use bevy_light_2d::prelude::*;
 use bevy_rapier2d::prelude::*;
 use syncopate::{
-    GameOptions, GameState, MainMenuState, PauseOptionsState, PauseState, camera,
+    GameState, MainMenuState, PauseOptionsState, PauseState,
+    camera::{
+        self,
+        effects::{self as camera_effects, CameraFocusEvent, CameraShakeEvent, CameraZoomEvent},
+        pixel_perfect,
+    },
+    chapters::{self, CurrentChapter},
     characters::{
-        thunwa::{self, ThunwaHealth},
-        zombie,
+        combat, flashlight,
+        health::{self, DamageEvent, DeathEvent},
+        sanity::{self, SanityEffectsTimer, ThunwaSanity},
+        thunwa::{self, ThunwaStamina},
+        weapon, zombie,
+    },
+    cutscene::{self, CutsceneEndedEvent, PlayCutsceneEvent},
+    dialogue::{self, DialogueEndedEvent, StartDialogueEvent, StoryFlags},
+    inventory::{self, Inventory, ItemPickedEvent, UseItemEvent, pickup},
+    lighting::{self, LightBlackoutEvent},
+    localization::{self, Localization},
+    objectives::{self, ObjectiveCompletedEvent, ObjectiveLog},
+    pause_physics_system,
+    sounds::{
+        self, AmbienceChannel, MusicChannel, SfxChannel, UiChannel, VoiceChannel,
+        adaptive_music::{self, ThreatLevel},
+        music_director::{self, MusicDirector},
+        sfx::{self, PlaySoundEvent},
+    },
+    terrains::{self, DoorOpenedEvent, ZoneEnteredEvent},
+    ui::{
+        self,
+        widgets::{self, FocusedSlider},
     },
-    pause_physics_system, sounds, terrains,
-    ui::{self, main_menu::MainMenuLightFlickerTimer},
 };
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
@@ -36,10 +61,18 @@ pub enum GameUpdateSet {
 fn main() {
     App::new()
         .insert_resource(ClearColor(Color::BLACK))
-        .insert_resource(GameOptions::default())
-        .insert_resource(MainMenuLightFlickerTimer::default())
-        .insert_resource(ThunwaHealth::default())
+        .insert_resource(syncopate::load_game_options())
+        .insert_resource(ThunwaStamina::default())
+        .insert_resource(Inventory::default())
+        .insert_resource(ThunwaSanity::default())
+        .insert_resource(SanityEffectsTimer::default())
         .insert_resource(ui::health_ui::HealthFlickerTimer::default())
+        .insert_resource(StoryFlags::default())
+        .insert_resource(ObjectiveLog::default())
+        .insert_resource(CurrentChapter::default())
+        .insert_resource(ThreatLevel::default())
+        .insert_resource(MusicDirector::default())
+        .insert_resource(FocusedSlider::default())
         .add_plugins((
             DefaultPlugins.set(WindowPlugin {
                 primary_window: Some(Window {
@@ -59,6 +92,11 @@ fn main() {
             Light2dPlugin,
             AudioPlugin,
         ))
+        .add_audio_channel::<MusicChannel>()
+        .add_audio_channel::<SfxChannel>()
+        .add_audio_channel::<AmbienceChannel>()
+        .add_audio_channel::<UiChannel>()
+        .add_audio_channel::<VoiceChannel>()
         // .add_plugins(FpsCounterPlugin)
         .add_plugins(EguiPlugin {
             enable_multipass_for_primary_context: true,
@@ -68,10 +106,27 @@ fn main() {
         .add_plugins(RapierDebugRenderPlugin::default())
         .add_plugins(AsepriteUltraPlugin)
         .add_plugins(TilemapPlugin)
+        .init_resource::<Localization>()
         .init_state::<GameState>()
         .init_state::<MainMenuState>()
         .init_state::<PauseState>()
         .init_state::<PauseOptionsState>()
+        .add_event::<DamageEvent>()
+        .add_event::<DeathEvent>()
+        .add_event::<ItemPickedEvent>()
+        .add_event::<UseItemEvent>()
+        .add_event::<LightBlackoutEvent>()
+        .add_event::<CameraShakeEvent>()
+        .add_event::<CameraZoomEvent>()
+        .add_event::<CameraFocusEvent>()
+        .add_event::<StartDialogueEvent>()
+        .add_event::<DialogueEndedEvent>()
+        .add_event::<PlayCutsceneEvent>()
+        .add_event::<CutsceneEndedEvent>()
+        .add_event::<DoorOpenedEvent>()
+        .add_event::<ZoneEnteredEvent>()
+        .add_event::<ObjectiveCompletedEvent>()
+        .add_event::<PlaySoundEvent>()
         .configure_sets(
             Startup,
             (
@@ -92,6 +147,43 @@ fn main() {
             )
                 .chain(),
         )
+        .add_systems(
+            Startup,
+            (
+                inventory::load_item_definitions,
+                dialogue::load_dialogues,
+                cutscene::load_cutscenes,
+                objectives::load_objectives,
+                chapters::load_chapters,
+                adaptive_music::load_music_tracks,
+                sfx::load_sound_effects,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                sounds::apply_channel_volumes,
+                syncopate::apply_display_options,
+                syncopate::save_game_options,
+            ),
+        )
+        .add_systems(Last, syncopate::limit_frame_rate)
+        .add_systems(
+            Update,
+            (
+                adaptive_music::drop_failed_music_stems,
+                music_director::direct_music,
+                music_director::update_track_volume,
+            )
+                .chain(),
+        )
+        .add_systems(Update, sfx::drop_failed_sound_effects)
+        .add_systems(
+            PostUpdate,
+            sfx::spatialize_sound_effects.after(AudioSystemSet::PlayTypedChannels),
+        )
+        .add_systems(OnEnter(PauseState::Paused), music_director::duck_music)
+        .add_systems(OnExit(PauseState::Paused), music_director::unduck_music)
         .add_systems(
             OnEnter(GameState::MainMenu),
             (
@@ -116,14 +208,33 @@ fn main() {
         .add_systems(
             Update,
             (
-                ui::screen_mode_button_handler,
-                ui::options::music_volume_button_handler,
+                widgets::toggle_button_handler,
+                widgets::update_toggle_buttons,
+                widgets::drag_sliders,
+                widgets::adjust_focused_slider,
+                widgets::update_sliders,
+                ui::options::back_button_handler,
                 ui::options::back_by_keyboard_input_handler,
             )
                 .in_set(GameUpdateSet::UI)
                 .run_if(in_state(GameState::MainMenu))
                 .run_if(in_state(MainMenuState::Options)),
         )
+        .add_systems(
+            OnEnter(MainMenuState::ChapterSelect),
+            ui::chapter_select::spawn_chapter_select.in_set(GameStartUpSet::UI),
+        )
+        .add_systems(
+            Update,
+            ui::chapter_select::chapter_button_handler
+                .in_set(GameUpdateSet::UI)
+                .run_if(in_state(GameState::MainMenu))
+                .run_if(in_state(MainMenuState::ChapterSelect)),
+        )
+        .add_systems(
+            OnExit(MainMenuState::ChapterSelect),
+            ui::chapter_select::despawn_chapter_select.in_set(GameUpdateSet::UI),
+        )
         .add_systems(
             OnExit(MainMenuState::Options),
             ui::options::despawn_options_menu.in_set(GameUpdateSet::UI),
@@ -140,11 +251,25 @@ fn main() {
         )
         .add_systems(
             Update,
-            ui::main_menu::light_flicker
-                .in_set(GameUpdateSet::UI)
-                .run_if(in_state(GameState::MainMenu)),
+            (
+                lighting::handle_light_blackout,
+                lighting::update_light_flicker,
+            )
+                .chain(),
         )
         .add_systems(Update, ui::ui_interaction.in_set(GameUpdateSet::UI))
+        .add_systems(Update, ui::update_ui_scale.after(GameUpdateSet::UI))
+        .add_systems(
+            Update,
+            (
+                localization::sync_language,
+                localization::fall_back_from_failed_fonts,
+                localization::update_localized_fonts,
+                localization::update_localized_text,
+            )
+                .chain()
+                .after(GameUpdateSet::UI),
+        )
         .add_systems(
             Update,
             ui::main_menu::button_pressed_handler
@@ -152,6 +277,7 @@ fn main() {
                 .run_if(in_state(GameState::MainMenu))
                 .run_if(in_state(MainMenuState::MainMenu)),
         )
+        .add_systems(OnEnter(GameState::Restarting), syncopate::finish_restart)
         .add_systems(
             OnEnter(GameState::InGame),
             syncopate::global_bevy_rapier_config.in_set(GameStartUpSet::Physics),
@@ -162,15 +288,22 @@ fn main() {
                 sounds::condo_entering::play_soundtrack,
                 terrains::condo_entering::draw_terrain,
             )
-                .in_set(GameStartUpSet::CondoEntering),
+                .in_set(GameStartUpSet::CondoEntering)
+                .run_if(chapters::in_scene("condo_entering")),
         )
         .add_systems(
             OnEnter(GameState::InGame),
             (
                 thunwa::setup_thunwa,
+                inventory::reset_inventory,
+                sanity::reset_sanity,
+                dialogue::reset_story_flags,
+                objectives::reset_objectives,
+                chapters::start_chapter,
                 zombie::setup_zombies,
                 camera::player_camera_setup,
                 ui::health_ui::spawn_health_ui,
+                ui::quest_log::spawn_objective_tracker,
             )
                 .in_set(GameStartUpSet::Thunwa),
         )
@@ -179,23 +312,55 @@ fn main() {
             (
                 terrains::condo_entering::despawn_condo_entering,
                 sounds::condo_entering::stop_playing_soundtrack,
+                adaptive_music::reset_threat_level,
+                sfx::despawn_one_shots_on_exit,
                 camera::despawn_player_camera,
+                pixel_perfect::despawn_pixel_perfect,
                 thunwa::despawn_thunwa,
                 zombie::despawn_zombies,
+                weapon::despawn_projectiles,
+                sanity::despawn_hallucinations,
+                sanity::reset_audio_distortion,
+                dialogue::close_dialogue,
+                cutscene::stop_cutscene,
+                ui::dialogue_box::despawn_dialogue_box,
                 ui::health_ui::despawn_health_ui,
+                ui::quest_log::despawn_objective_tracker,
             )
                 .in_set(GameUpdateSet::CondoEntering),
         )
         .add_systems(
             Update,
-            thunwa::thunwa_camera_following
-                .in_set(GameUpdateSet::Thunwa)
+            (
+                camera_effects::handle_camera_effect_events,
+                camera::update_camera_follow,
+                camera_effects::update_camera_effects,
+                pixel_perfect::snap_camera_to_pixels,
+            )
+                .chain()
+                .in_set(GameUpdateSet::Camera)
                 .run_if(in_state(GameState::InGame))
                 .run_if(in_state(PauseState::InGame)),
         )
         .add_systems(
             Update,
-            thunwa::thunwa_movement
+            (
+                pixel_perfect::sync_pixel_perfect_mode,
+                pixel_perfect::fit_pixel_perfect_canvas,
+            )
+                .chain()
+                .in_set(GameUpdateSet::Camera)
+                .run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                thunwa::thunwa_dodge
+                    .run_if(not(dialogue::dialogue_open))
+                    .run_if(not(cutscene::cutscene_playing)),
+                thunwa::thunwa_movement,
+            )
+                .chain()
                 .in_set(GameUpdateSet::Thunwa)
                 .after(GameStartUpSet::Thunwa)
                 .run_if(in_state(GameState::InGame))
@@ -204,18 +369,187 @@ fn main() {
         .add_systems(
             Update,
             (
-                zombie::update_zombie_ai,
-                zombie::zombie_attack_system,
+                zombie::update_zombie_ai.run_if(not(cutscene::cutscene_playing)),
+                zombie::zombie_attack_system.run_if(not(cutscene::cutscene_playing)),
                 zombie::update_zombie_animation_direction,
                 ui::health_ui::update_health_ui,
                 ui::health_ui::update_health_bar_color,
                 ui::health_ui::update_health_flicker,
+                ui::health_ui::update_stamina_ui,
+                ui::health_ui::update_ammo_ui,
+                ui::health_ui::update_battery_ui,
+                ui::health_ui::update_sanity_ui,
+                ui::quest_log::update_objective_tracker,
             )
                 .in_set(GameUpdateSet::Zombie)
                 .after(GameStartUpSet::Thunwa)
                 .run_if(in_state(GameState::InGame))
                 .run_if(in_state(PauseState::InGame)),
         )
+        .add_systems(
+            Update,
+            (
+                health::update_health,
+                combat::update_hit_stun,
+                camera_effects::camera_shake_on_damage
+                    .after(zombie::zombie_attack_system)
+                    .after(weapon::projectile_hit_system)
+                    .before(health::apply_damage),
+                weapon::update_projectiles,
+                weapon::projectile_hit_system.before(health::apply_damage),
+                health::apply_damage.after(zombie::zombie_attack_system),
+                health::despawn_on_death.after(health::apply_damage),
+                thunwa::thunwa_death_handler.after(health::apply_damage),
+            )
+                .in_set(GameUpdateSet::Zombie)
+                .after(GameStartUpSet::Thunwa)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                weapon::update_aim,
+                weapon::reload_weapon,
+                weapon::fire_weapon,
+            )
+                .chain()
+                .in_set(GameUpdateSet::Thunwa)
+                .after(thunwa::thunwa_movement)
+                .run_if(not(dialogue::dialogue_open))
+                .run_if(not(cutscene::cutscene_playing))
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                flashlight::toggle_flashlight,
+                flashlight::update_flashlight_battery,
+                flashlight::orient_flashlight,
+            )
+                .chain()
+                .in_set(GameUpdateSet::Thunwa)
+                .after(weapon::update_aim)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                sanity::update_sanity,
+                sanity::apply_control_wobble
+                    .after(thunwa::thunwa_movement)
+                    .run_if(not(cutscene::cutscene_playing)),
+                sanity::apply_audio_distortion,
+                sanity::spawn_hallucinations,
+                sanity::update_hallucinations,
+            )
+                .in_set(GameUpdateSet::Thunwa)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                cutscene::cutscene_trigger_system,
+                cutscene::play_cutscene_handler,
+                (
+                    cutscene::skip_cutscene,
+                    cutscene::run_cutscene,
+                    cutscene::hold_zombies,
+                )
+                    .chain()
+                    .run_if(cutscene::cutscene_playing),
+            )
+                .chain()
+                .in_set(GameUpdateSet::Thunwa)
+                .after(thunwa::thunwa_dodge)
+                .before(dialogue::dialogue_trigger_system)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                dialogue::dialogue_trigger_system.run_if(not(cutscene::cutscene_playing)),
+                dialogue::start_dialogue_handler,
+                (
+                    dialogue::update_typewriter,
+                    dialogue::advance_dialogue,
+                    ui::dialogue_box::spawn_dialogue_box,
+                    ui::dialogue_box::update_dialogue_box,
+                )
+                    .chain()
+                    .run_if(dialogue::dialogue_open),
+                ui::dialogue_box::despawn_dialogue_box.run_if(not(dialogue::dialogue_open)),
+            )
+                .chain()
+                .in_set(GameUpdateSet::Thunwa)
+                .after(thunwa::thunwa_dodge)
+                .before(thunwa::thunwa_movement)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                terrains::detect_zone_entry,
+                terrains::condo_entering::open_condo_door
+                    .run_if(chapters::in_scene("condo_entering"))
+                    .run_if(not(dialogue::dialogue_open))
+                    .run_if(not(cutscene::cutscene_playing)),
+                objectives::track_objectives
+                    .after(terrains::detect_zone_entry)
+                    .after(terrains::condo_entering::open_condo_door)
+                    .after(pickup::pickup_items),
+                chapters::update_chapter_completion.after(objectives::track_objectives),
+            )
+                .in_set(GameUpdateSet::Thunwa)
+                .after(thunwa::thunwa_movement)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                adaptive_music::update_threat_level.run_if(in_state(PauseState::InGame)),
+                adaptive_music::crossfade_music_layers
+                    .run_if(adaptive_music::adaptive_music_playing),
+            )
+                .chain()
+                .after(GameUpdateSet::Zombie)
+                .run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                (sfx::play_footsteps, sfx::zombie_groans).run_if(in_state(PauseState::InGame)),
+                sfx::play_sound_effects,
+                sfx::prune_finished_sounds,
+                sfx::despawn_one_shot_emitters,
+            )
+                .chain()
+                .after(GameUpdateSet::Zombie)
+                .run_if(in_state(GameState::InGame)),
+        )
+        .add_systems(
+            Update,
+            pickup::pickup_items
+                .in_set(GameUpdateSet::Thunwa)
+                .after(thunwa::thunwa_movement)
+                .run_if(in_state(GameState::InGame))
+                .run_if(in_state(PauseState::InGame)),
+        )
+        .add_systems(
+            Update,
+            (
+                terrains::generate_light_occluders,
+                terrains::apply_scene_ambient_light,
+            )
+                .in_set(GameUpdateSet::CondoEntering)
+                .run_if(in_state(GameState::InGame)),
+        )
         .add_systems(
             Update,
             terrains::condo_entering::update_z_order
@@ -228,9 +562,39 @@ fn main() {
             Update,
             ui::paused_menu::pause_handler
                 .in_set(GameUpdateSet::UI)
+                .before(cutscene::skip_cutscene)
+                .run_if(not(cutscene::cutscene_playing))
                 .run_if(in_state(PauseState::InGame))
                 .run_if(in_state(PauseOptionsState::None)),
         )
+        .add_systems(
+            Update,
+            ui::inventory_menu::open_inventory_handler
+                .in_set(GameUpdateSet::UI)
+                .run_if(not(cutscene::cutscene_playing))
+                .run_if(in_state(PauseState::InGame))
+                .run_if(in_state(PauseOptionsState::None)),
+        )
+        .add_systems(
+            OnEnter(PauseOptionsState::Inventory),
+            ui::inventory_menu::spawn_inventory_menu.in_set(GameStartUpSet::UI),
+        )
+        .add_systems(
+            OnExit(PauseOptionsState::Inventory),
+            ui::inventory_menu::despawn_inventory_menu.in_set(GameUpdateSet::UI),
+        )
+        .add_systems(
+            Update,
+            (
+                ui::inventory_menu::inventory_slot_handler,
+                inventory::use_item_handler,
+                ui::inventory_menu::refresh_inventory_menu,
+                ui::inventory_menu::close_inventory_handler,
+            )
+                .chain()
+                .in_set(GameUpdateSet::UI)
+                .run_if(in_state(PauseOptionsState::Inventory)),
+        )
         .add_systems(
             Update,
             pause_physics_system.run_if(in_state(GameState::InGame)),
@@ -257,6 +621,20 @@ fn main() {
             OnExit(PauseOptionsState::Paused),
             ui::paused_menu::despawn_paused_menu.in_set(GameUpdateSet::UI),
         )
+        .add_systems(
+            OnEnter(PauseOptionsState::QuestLog),
+            ui::quest_log::spawn_quest_log.in_set(GameStartUpSet::UI),
+        )
+        .add_systems(
+            OnExit(PauseOptionsState::QuestLog),
+            ui::quest_log::despawn_quest_log.in_set(GameUpdateSet::UI),
+        )
+        .add_systems(
+            Update,
+            ui::quest_log::back_to_paused_menu_handler
+                .in_set(GameUpdateSet::UI)
+                .run_if(in_state(PauseOptionsState::QuestLog)),
+        )
         .add_systems(
             OnEnter(PauseOptionsState::Options),
             (ui::in_game_options_menu::spawn_paused_options_menu,).in_set(GameStartUpSet::UI),
@@ -268,8 +646,12 @@ fn main() {
         .add_systems(
             Update,
             (
-                ui::screen_mode_button_handler,
-                ui::in_game_options_menu::music_volume_button_handler,
+                widgets::toggle_button_handler,
+                widgets::update_toggle_buttons,
+                widgets::drag_sliders,
+                widgets::adjust_focused_slider,
+                widgets::update_sliders,
+                ui::in_game_options_menu::back_button_handler,
                 ui::in_game_options_menu::back_to_options_handler,
             )
                 .in_set(GameUpdateSet::UI)
@@ -0,0 +1,285 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    chapters::FIRST_CHAPTER,
+    dialogue::{DialogueEndedEvent, StoryEffect, StoryFlags},
+    inventory::ItemPickedEvent,
+    load_data_file,
+    terrains::{DoorOpenedEvent, ZoneEnteredEvent},
+};
+
+const OBJECTIVES_PATH: &str = "data/objectives.ron";
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum ObjectiveCondition {
+    DoorOpened(String),
+    ZoneEntered(String),
+    ItemPicked(String),
+    DialogueFinished(String),
+    StoryFlag(String),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ObjectiveDefinition {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub condition: ObjectiveCondition,
+    // Objectives that have to be done before this one shows up
+    #[serde(default)]
+    pub requires: Vec<String>,
+    #[serde(default)]
+    pub optional: bool,
+    // Applied once the objective is completed
+    #[serde(default)]
+    pub effects: Vec<StoryEffect>,
+}
+
+// Objectives of every chapter, keyed by chapter id
+#[derive(Resource, Default, Debug)]
+pub struct ObjectiveDefinitions(pub HashMap<String, Vec<ObjectiveDefinition>>);
+
+impl ObjectiveDefinitions {
+    pub fn chapter(&self, chapter: &str) -> &[ObjectiveDefinition] {
+        self.0.get(chapter).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct ObjectiveLog {
+    pub chapter: String,
+    pub completed: HashSet<String>,
+}
+
+impl Default for ObjectiveLog {
+    fn default() -> Self {
+        Self {
+            chapter: FIRST_CHAPTER.to_string(),
+            completed: HashSet::new(),
+        }
+    }
+}
+
+impl ObjectiveLog {
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.contains(id)
+    }
+
+    // Unlocked means every objective it requires is done
+    pub fn is_unlocked(&self, objective: &ObjectiveDefinition) -> bool {
+        objective
+            .requires
+            .iter()
+            .all(|required| self.is_completed(required))
+    }
+
+    pub fn active<'a>(
+        &'a self,
+        definitions: &'a ObjectiveDefinitions,
+    ) -> impl Iterator<Item = &'a ObjectiveDefinition> {
+        definitions
+            .chapter(&self.chapter)
+            .iter()
+            .filter(|objective| !self.is_completed(&objective.id) && self.is_unlocked(objective))
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct ObjectiveCompletedEvent {
+    pub id: String,
+}
+
+pub fn load_objectives(mut commands: Commands) {
+    let objectives: HashMap<String, Vec<ObjectiveDefinition>> =
+        load_data_file(OBJECTIVES_PATH).unwrap_or_default();
+
+    commands.insert_resource(ObjectiveDefinitions(objectives));
+}
+
+pub fn reset_objectives(mut objective_log: ResMut<ObjectiveLog>) {
+    objective_log.completed.clear();
+}
+
+// The gameplay events objective conditions listen for
+#[derive(SystemParam)]
+pub struct ObjectiveEvents<'w, 's> {
+    doors: EventReader<'w, 's, DoorOpenedEvent>,
+    zones: EventReader<'w, 's, ZoneEnteredEvent>,
+    items: EventReader<'w, 's, ItemPickedEvent>,
+    dialogues: EventReader<'w, 's, DialogueEndedEvent>,
+}
+
+impl ObjectiveEvents<'_, '_> {
+    // Everything that happened since the last read, as the conditions it would satisfy
+    fn happened(&mut self) -> Vec<ObjectiveCondition> {
+        self.doors
+            .read()
+            .map(|event| ObjectiveCondition::DoorOpened(event.door.clone()))
+            .chain(
+                self.zones
+                    .read()
+                    .map(|event| ObjectiveCondition::ZoneEntered(event.zone.clone())),
+            )
+            .chain(
+                self.items
+                    .read()
+                    .map(|event| ObjectiveCondition::ItemPicked(event.item.clone())),
+            )
+            .chain(
+                self.dialogues
+                    .read()
+                    .map(|event| ObjectiveCondition::DialogueFinished(event.dialogue.clone())),
+            )
+            .collect()
+    }
+}
+
+pub fn track_objectives(
+    definitions: Res<ObjectiveDefinitions>,
+    mut objective_log: ResMut<ObjectiveLog>,
+    mut story_flags: ResMut<StoryFlags>,
+    mut events: ObjectiveEvents,
+    mut completed_events: EventWriter<ObjectiveCompletedEvent>,
+) {
+    let happened = events.happened();
+
+    // Completing one objective can unlock the next, so keep going until nothing changes
+    loop {
+        let newly_completed: Vec<ObjectiveDefinition> = objective_log
+            .active(&definitions)
+            .filter(|objective| match &objective.condition {
+                ObjectiveCondition::StoryFlag(flag) => story_flags.is_set(flag),
+                condition => happened.contains(condition),
+            })
+            .cloned()
+            .collect();
+
+        if newly_completed.is_empty() {
+            return;
+        }
+
+        for objective in newly_completed {
+            story_flags.apply(&objective.effects);
+            objective_log.completed.insert(objective.id.clone());
+            completed_events.write(ObjectiveCompletedEvent { id: objective.id });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objective(
+        id: &str,
+        condition: ObjectiveCondition,
+        requires: &[&str],
+    ) -> ObjectiveDefinition {
+        ObjectiveDefinition {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            condition,
+            requires: requires.iter().map(|id| id.to_string()).collect(),
+            optional: false,
+            effects: vec![],
+        }
+    }
+
+    fn definitions() -> ObjectiveDefinitions {
+        let mut open_door = objective(
+            "open_door",
+            ObjectiveCondition::DoorOpened("front_door".to_string()),
+            &[],
+        );
+        open_door.effects = vec![StoryEffect::SetFlag("inside".to_string())];
+
+        ObjectiveDefinitions(HashMap::from([(
+            FIRST_CHAPTER.to_string(),
+            vec![
+                open_door,
+                objective(
+                    "get_inside",
+                    ObjectiveCondition::StoryFlag("inside".to_string()),
+                    &["open_door"],
+                ),
+                objective(
+                    "find_key",
+                    ObjectiveCondition::ItemPicked("key".to_string()),
+                    &["open_door", "get_inside"],
+                ),
+            ],
+        )]))
+    }
+
+    fn active_ids(objective_log: &ObjectiveLog, definitions: &ObjectiveDefinitions) -> Vec<String> {
+        objective_log
+            .active(definitions)
+            .map(|objective| objective.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn objectives_unlock_once_every_requirement_is_done() {
+        let definitions = definitions();
+        let mut objective_log = ObjectiveLog::default();
+
+        assert_eq!(active_ids(&objective_log, &definitions), vec!["open_door"]);
+
+        objective_log.completed.insert("open_door".to_string());
+        assert_eq!(active_ids(&objective_log, &definitions), vec!["get_inside"]);
+
+        objective_log.completed.insert("get_inside".to_string());
+        assert_eq!(active_ids(&objective_log, &definitions), vec!["find_key"]);
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_event::<DoorOpenedEvent>()
+            .add_event::<ZoneEnteredEvent>()
+            .add_event::<ItemPickedEvent>()
+            .add_event::<DialogueEndedEvent>()
+            .add_event::<ObjectiveCompletedEvent>()
+            .insert_resource(definitions())
+            .init_resource::<ObjectiveLog>()
+            .init_resource::<StoryFlags>()
+            .add_systems(Update, track_objectives);
+        app
+    }
+
+    #[test]
+    fn completing_an_objective_carries_on_to_the_ones_it_unlocks() {
+        let mut app = app();
+
+        app.world_mut().send_event(DoorOpenedEvent {
+            door: "front_door".to_string(),
+        });
+        app.update();
+
+        let objective_log = app.world().resource::<ObjectiveLog>();
+        assert!(objective_log.is_completed("open_door"));
+        // Unlocked by the door and completed by the flag the door sets
+        assert!(objective_log.is_completed("get_inside"));
+        assert!(!objective_log.is_completed("find_key"));
+        assert!(app.world().resource::<StoryFlags>().is_set("inside"));
+
+        let completed_events = app.world().resource::<Events<ObjectiveCompletedEvent>>();
+        assert_eq!(completed_events.len(), 2);
+    }
+
+    #[test]
+    fn locked_objectives_ignore_their_condition() {
+        let mut app = app();
+
+        app.world_mut().send_event(ItemPickedEvent {
+            item: "key".to_string(),
+            count: 1,
+        });
+        app.update();
+
+        assert!(app.world().resource::<ObjectiveLog>().completed.is_empty());
+    }
+}
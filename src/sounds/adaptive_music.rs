@@ -0,0 +1,281 @@
+use std::{collections::HashMap, time::Duration};
+
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+use bevy_kira_audio::{AudioSource, prelude::*};
+use bevy_rapier2d::prelude::Velocity;
+use serde::Deserialize;
+
+use crate::{
+    GameOptions,
+    characters::{thunwa::Thunwa, zombie::Zombie},
+    load_data_file,
+    sounds::{
+        MusicChannel, VolumeSetting,
+        music_director::{CROSSFADE_MILLIS, MusicDirector},
+    },
+};
+
+const MUSIC_TRACKS_PATH: &str = "data/music.ron";
+
+// Zombies further away than this don't add to the threat at all
+const THREAT_RADIUS: f32 = 480.0;
+// A zombie that isn't closing in only counts for part of its proximity
+const IDLE_THREAT_FACTOR: f32 = 0.5;
+// Every chasing zombie past the closest one adds a bit on top
+const EXTRA_CHASER_THREAT: f32 = 0.15;
+// Threat rises quickly and lingers for a while after escaping
+const THREAT_RISE_RATE: f32 = 1.5;
+const THREAT_FALL_RATE: f32 = 0.2;
+// Small volume changes are skipped instead of sending a tween every frame
+const VOLUME_EPSILON: f64 = 0.005;
+const LAYER_TWEEN_MILLIS: u64 = 250;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicLayer {
+    Ambient,
+    Percussion,
+    Lead,
+}
+
+impl MusicLayer {
+    // How loud the layer plays for a threat between 0.0 and 1.0
+    pub fn volume(&self, threat: f32) -> f32 {
+        match self {
+            MusicLayer::Ambient => 1.0 - 0.4 * threat,
+            MusicLayer::Percussion => smoothstep(0.15, 0.5, threat),
+            MusicLayer::Lead => smoothstep(0.55, 0.9, threat),
+        }
+    }
+}
+
+fn smoothstep(edge_0: f32, edge_1: f32, x: f32) -> f32 {
+    let t = ((x - edge_0) / (edge_1 - edge_0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MusicStemDefinition {
+    pub layer: MusicLayer,
+    pub path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MusicStem {
+    pub layer: MusicLayer,
+    pub source: Handle<AudioSource>,
+}
+
+// Stems of every adaptive track, keyed by track id. They are loaded up front so every stem of a
+// track is ready to start in the same frame
+#[derive(Resource, Default, Debug)]
+pub struct MusicTracks(pub HashMap<String, Vec<MusicStem>>);
+
+#[derive(Resource, Default, Debug)]
+pub struct ThreatLevel {
+    // Smoothed value the music follows
+    pub value: f32,
+    pub target: f32,
+}
+
+#[derive(Debug)]
+struct PlayingStem {
+    layer: MusicLayer,
+    instance: Handle<AudioInstance>,
+    volume: f64,
+}
+
+// Only exists while an adaptive track is playing
+#[derive(Resource, Debug)]
+pub struct AdaptiveMusic {
+    pub track: String,
+    stems: Vec<PlayingStem>,
+    // The first volume change crossfades in with whatever track played before
+    fading_in: bool,
+}
+
+pub fn load_music_tracks(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let definitions: HashMap<String, Vec<MusicStemDefinition>> =
+        load_data_file(MUSIC_TRACKS_PATH).unwrap_or_default();
+
+    let tracks = definitions
+        .into_iter()
+        .map(|(track, stems)| {
+            let stems = stems
+                .into_iter()
+                .map(|stem| MusicStem {
+                    layer: stem.layer,
+                    source: asset_server.load(stem.path),
+                })
+                .collect();
+
+            (track, stems)
+        })
+        .collect();
+
+    commands.insert_resource(MusicTracks(tracks));
+}
+
+// Same as sound effects, a stem that failed to load would never start, the track plays without it
+pub fn drop_failed_music_stems(
+    mut failed_events: EventReader<AssetLoadFailedEvent<AudioSource>>,
+    mut tracks: ResMut<MusicTracks>,
+) {
+    for event in failed_events.read() {
+        for stems in tracks.0.values_mut() {
+            if stems.iter().any(|stem| stem.source.id() == event.id) {
+                println!("❌ Failed to load music stem {}", event.path);
+                stems.retain(|stem| stem.source.id() != event.id);
+            }
+        }
+    }
+}
+
+// Starts every stem in the same frame so they stay in sync, the layers fade in from silence
+pub fn play_adaptive_track(
+    commands: &mut Commands,
+    music_channel: &AudioChannel<MusicChannel>,
+    tracks: &MusicTracks,
+    track: &str,
+    start_from: f64,
+) {
+    let Some(stems) = tracks.0.get(track) else {
+        println!("❌ Unknown music track {track}");
+        return;
+    };
+
+    let stems = stems
+        .iter()
+        .map(|stem| PlayingStem {
+            layer: stem.layer,
+            instance: music_channel
+                .play(stem.source.clone())
+                .with_volume(0.0)
+                .start_from(start_from)
+                .looped()
+                .handle(),
+            volume: 0.0,
+        })
+        .collect();
+
+    commands.insert_resource(AdaptiveMusic {
+        track: track.to_string(),
+        stems,
+        fading_in: true,
+    });
+}
+
+// Returns how far into the track the stems got, they all play in sync so any one of them will do
+pub fn fade_out_adaptive_track(
+    commands: &mut Commands,
+    adaptive_music: &AdaptiveMusic,
+    audio_instances: &mut Assets<AudioInstance>,
+    fade_millis: u64,
+) -> Option<f64> {
+    let mut position = None;
+
+    for stem in adaptive_music.stems.iter() {
+        if let Some(instance) = audio_instances.get_mut(&stem.instance) {
+            position = position.or(instance.state().position());
+            instance.stop(AudioTween::linear(Duration::from_millis(fade_millis)));
+        }
+    }
+
+    commands.remove_resource::<AdaptiveMusic>();
+    position
+}
+
+pub fn reset_threat_level(mut threat_level: ResMut<ThreatLevel>) {
+    *threat_level = ThreatLevel::default();
+}
+
+pub fn adaptive_music_playing(adaptive_music: Option<Res<AdaptiveMusic>>) -> bool {
+    adaptive_music.is_some()
+}
+
+pub fn update_threat_level(
+    time: Res<Time>,
+    mut threat_level: ResMut<ThreatLevel>,
+    thunwa_query: Query<&Transform, With<Thunwa>>,
+    zombie_query: Query<(&Transform, &Velocity), With<Zombie>>,
+) {
+    threat_level.target = match thunwa_query.single() {
+        Ok(thunwa_transform) => {
+            let thunwa_pos = thunwa_transform.translation.xy();
+            let mut closest: f32 = 0.0;
+            let mut chasers: u32 = 0;
+
+            for (zombie_transform, velocity) in zombie_query.iter() {
+                let offset = thunwa_pos - zombie_transform.translation.xy();
+                let proximity = (1.0 - offset.length() / THREAT_RADIUS).clamp(0.0, 1.0);
+                if proximity <= 0.0 {
+                    continue;
+                }
+
+                // Chasing means actually moving towards Thunwa
+                let chasing = velocity.linvel.length() > 1.0 && velocity.linvel.dot(offset) > 0.0;
+                if chasing {
+                    chasers += 1;
+                    closest = closest.max(proximity);
+                } else {
+                    closest = closest.max(proximity * IDLE_THREAT_FACTOR);
+                }
+            }
+
+            let extra = chasers.saturating_sub(1) as f32 * EXTRA_CHASER_THREAT;
+            (closest + extra).clamp(0.0, 1.0)
+        }
+        Err(_) => 0.0,
+    };
+
+    let rate = if threat_level.target > threat_level.value {
+        THREAT_RISE_RATE
+    } else {
+        THREAT_FALL_RATE
+    };
+    let step = rate * time.delta_secs();
+    let delta = (threat_level.target - threat_level.value).clamp(-step, step);
+
+    if delta != 0.0 {
+        threat_level.value += delta;
+    }
+}
+
+pub fn crossfade_music_layers(
+    threat_level: Res<ThreatLevel>,
+    game_options: Res<GameOptions>,
+    music_director: Res<MusicDirector>,
+    mut adaptive_music: ResMut<AdaptiveMusic>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let tween_millis = if adaptive_music.fading_in {
+        CROSSFADE_MILLIS
+    } else {
+        LAYER_TWEEN_MILLIS
+    };
+    let mut all_started = true;
+
+    for stem in adaptive_music.stems.iter_mut() {
+        let volume = stem.layer.volume(threat_level.value) as f64
+            * game_options.channel_volume(VolumeSetting::Music)
+            * music_director.volume_scale();
+        if (volume - stem.volume).abs() < VOLUME_EPSILON {
+            continue;
+        }
+
+        // Instances only show up once the audio systems have started them
+        let Some(instance) = audio_instances.get_mut(&stem.instance) else {
+            all_started = false;
+            continue;
+        };
+
+        instance.set_volume(
+            volume,
+            AudioTween::linear(Duration::from_millis(tween_millis)),
+        );
+        stem.volume = volume;
+    }
+
+    if adaptive_music.fading_in && all_started {
+        adaptive_music.fading_in = false;
+    }
+}
@@ -1,19 +1,13 @@
 use bevy::prelude::*;
-use bevy_kira_audio::prelude::*;
 
-use crate::GameOptions;
+use crate::sounds::music_director::{MusicCue, MusicDirector};
 
-pub fn play_soundtrack(
-    asset_server: Res<AssetServer>,
-    game_options: Res<GameOptions>,
-    audio: Res<Audio>,
-) {
-    audio
-        .play(asset_server.load("soundtracks/main_menu/bro_turned_into_horror.ogg"))
-        .with_volume(game_options.music_volume)
-        .looped();
+const CONDO_ENTERING_TRACK: &str = "condo_entering";
+
+pub fn play_soundtrack(mut music_director: ResMut<MusicDirector>) {
+    music_director.play(MusicCue::Adaptive(CONDO_ENTERING_TRACK.to_string()));
 }
 
-pub fn stop_playing_soundtrack(audio: Res<Audio>) {
-    audio.stop();
+pub fn stop_playing_soundtrack(mut music_director: ResMut<MusicDirector>) {
+    music_director.stop();
 }
@@ -1,19 +1,14 @@
 use bevy::prelude::*;
-use bevy_kira_audio::prelude::*;
 
-use crate::GameOptions;
+use crate::sounds::music_director::{MusicCue, MusicDirector};
 
-pub fn play_soundtrack(
-    asset_server: Res<AssetServer>,
-    game_options: Res<GameOptions>,
-    audio: Res<Audio>,
-) {
-    audio
-        .play(asset_server.load("soundtracks/main_menu/ready_or_not.ogg"))
-        .with_volume(game_options.music_volume)
-        .looped();
+const MAIN_MENU_TRACK: &str = "soundtracks/main_menu/ready_or_not.ogg";
+
+pub fn play_soundtrack(mut music_director: ResMut<MusicDirector>) {
+    music_director.play(MusicCue::Track(MAIN_MENU_TRACK.to_string()));
 }
 
-pub fn stop_playing_soundtrack(audio: Res<Audio>) {
-    audio.stop();
+// Only the music fades out, anything still playing on the other channels carries on
+pub fn stop_playing_soundtrack(mut music_director: ResMut<MusicDirector>) {
+    music_director.stop();
 }
@@ -1,2 +1,105 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::GameOptions;
+
+pub mod adaptive_music;
 pub mod condo_entering;
 pub mod main_menu;
+pub mod music_director;
+pub mod sfx;
+
+// Every sound plays on one of these, so each kind can be turned down or stopped on its own
+#[derive(Resource)]
+pub struct MusicChannel;
+
+#[derive(Resource)]
+pub struct SfxChannel;
+
+#[derive(Resource)]
+pub struct AmbienceChannel;
+
+#[derive(Resource)]
+pub struct UiChannel;
+
+#[derive(Resource)]
+pub struct VoiceChannel;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VolumeSetting {
+    Master,
+    Music,
+    Sfx,
+    Ambience,
+    Ui,
+    Voice,
+}
+
+impl VolumeSetting {
+    pub const ALL: [VolumeSetting; 6] = [
+        VolumeSetting::Master,
+        VolumeSetting::Music,
+        VolumeSetting::Sfx,
+        VolumeSetting::Ambience,
+        VolumeSetting::Ui,
+        VolumeSetting::Voice,
+    ];
+
+    // String table key of the slider label
+    pub fn label(&self) -> &'static str {
+        match self {
+            VolumeSetting::Master => "options.master_volume",
+            VolumeSetting::Music => "options.music_volume",
+            VolumeSetting::Sfx => "options.sfx_volume",
+            VolumeSetting::Ambience => "options.ambience_volume",
+            VolumeSetting::Ui => "options.ui_volume",
+            VolumeSetting::Voice => "options.voice_volume",
+        }
+    }
+}
+
+// Music and SFX scale the volume of each instance themselves (threat layers, ducking, distance),
+// and a channel volume overwrites every instance on the channel, so those two stay at 1.0. The
+// other channels are only touched when their own volume changed.
+pub fn apply_channel_volumes(
+    game_options: Res<GameOptions>,
+    ambience_channel: Res<AudioChannel<AmbienceChannel>>,
+    ui_channel: Res<AudioChannel<UiChannel>>,
+    voice_channel: Res<AudioChannel<VoiceChannel>>,
+    mut applied: Local<HashMap<VolumeSetting, f64>>,
+) {
+    if !game_options.is_changed() {
+        return;
+    }
+
+    set_channel_volume(
+        &ambience_channel,
+        VolumeSetting::Ambience,
+        &game_options,
+        &mut applied,
+    );
+    set_channel_volume(&ui_channel, VolumeSetting::Ui, &game_options, &mut applied);
+    set_channel_volume(
+        &voice_channel,
+        VolumeSetting::Voice,
+        &game_options,
+        &mut applied,
+    );
+}
+
+fn set_channel_volume<T: Resource>(
+    channel: &AudioChannel<T>,
+    setting: VolumeSetting,
+    game_options: &GameOptions,
+    applied: &mut HashMap<VolumeSetting, f64>,
+) {
+    let volume = game_options.channel_volume(setting);
+
+    if applied.get(&setting) != Some(&volume) {
+        channel.set_volume(volume);
+        applied.insert(setting, volume);
+    }
+}
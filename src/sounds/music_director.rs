@@ -0,0 +1,181 @@
+use std::{collections::HashMap, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_kira_audio::prelude::*;
+
+use crate::{
+    GameOptions,
+    sounds::{
+        MusicChannel, VolumeSetting,
+        adaptive_music::{self, AdaptiveMusic, MusicTracks},
+    },
+};
+
+pub const CROSSFADE_MILLIS: u64 = 1500;
+const DUCK_MILLIS: u64 = 400;
+// Music keeps playing under the pause menu, just quieter
+const DUCKED_VOLUME: f64 = 0.35;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MusicCue {
+    // A single looping file, e.g. the main menu theme
+    Track(String),
+    // Layered stems from data/music.ron that follow the threat level
+    Adaptive(String),
+}
+
+// Every system asks the director for music instead of using the music channel directly, so
+// changing tracks always crossfades
+#[derive(Resource, Default, Debug)]
+pub struct MusicDirector {
+    requested: Option<MusicCue>,
+    current: Option<MusicCue>,
+    // Only set while a `MusicCue::Track` is playing
+    instance: Option<Handle<AudioInstance>>,
+    // Where each cue was when it got faded out, so coming back to it carries on from there
+    positions: HashMap<String, f64>,
+    ducked: bool,
+}
+
+impl MusicDirector {
+    pub fn play(&mut self, cue: MusicCue) {
+        self.requested = Some(cue);
+    }
+
+    pub fn stop(&mut self) {
+        self.requested = None;
+    }
+
+    pub fn volume_scale(&self) -> f64 {
+        if self.ducked { DUCKED_VOLUME } else { 1.0 }
+    }
+}
+
+fn crossfade() -> AudioTween {
+    AudioTween::linear(Duration::from_millis(CROSSFADE_MILLIS))
+}
+
+fn cue_key(cue: &MusicCue) -> &str {
+    match cue {
+        MusicCue::Track(path) => path,
+        MusicCue::Adaptive(track) => track,
+    }
+}
+
+// What starting a cue needs, whether it is a single track or adaptive stems
+#[derive(SystemParam)]
+pub struct MusicSources<'w> {
+    asset_server: Res<'w, AssetServer>,
+    tracks: Res<'w, MusicTracks>,
+    game_options: Res<'w, GameOptions>,
+    music_channel: Res<'w, AudioChannel<MusicChannel>>,
+}
+
+pub fn direct_music(
+    mut commands: Commands,
+    mut music_director: ResMut<MusicDirector>,
+    sources: MusicSources,
+    adaptive_music: Option<Res<AdaptiveMusic>>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    if music_director.requested == music_director.current {
+        return;
+    }
+
+    // Fade out whatever is playing and remember how far it got
+    let position = match music_director.current.clone() {
+        Some(MusicCue::Track(_)) => music_director
+            .instance
+            .take()
+            .and_then(|instance| audio_instances.get_mut(&instance))
+            .and_then(|instance| {
+                let position = instance.state().position();
+                instance.stop(crossfade());
+                position
+            }),
+        Some(MusicCue::Adaptive(_)) => adaptive_music.and_then(|adaptive_music| {
+            adaptive_music::fade_out_adaptive_track(
+                &mut commands,
+                &adaptive_music,
+                &mut audio_instances,
+                CROSSFADE_MILLIS,
+            )
+        }),
+        None => None,
+    };
+
+    if let (Some(cue), Some(position)) = (music_director.current.clone(), position) {
+        music_director
+            .positions
+            .insert(cue_key(&cue).to_string(), position);
+    }
+
+    let requested = music_director.requested.clone();
+    let start_from = requested
+        .as_ref()
+        .and_then(|cue| music_director.positions.get(cue_key(cue)))
+        .copied()
+        .unwrap_or(0.0);
+
+    match &requested {
+        Some(MusicCue::Track(path)) => {
+            music_director.instance = Some(
+                sources
+                    .music_channel
+                    .play(sources.asset_server.load(path.clone()))
+                    .with_volume(
+                        sources.game_options.channel_volume(VolumeSetting::Music)
+                            * music_director.volume_scale(),
+                    )
+                    .start_from(start_from)
+                    .fade_in(crossfade())
+                    .looped()
+                    .handle(),
+            );
+        }
+        Some(MusicCue::Adaptive(track)) => {
+            adaptive_music::play_adaptive_track(
+                &mut commands,
+                &sources.music_channel,
+                &sources.tracks,
+                track,
+                start_from,
+            );
+        }
+        None => {}
+    }
+
+    music_director.current = requested;
+}
+
+pub fn duck_music(mut music_director: ResMut<MusicDirector>) {
+    music_director.ducked = true;
+}
+
+pub fn unduck_music(mut music_director: ResMut<MusicDirector>) {
+    music_director.ducked = false;
+}
+
+// Adaptive stems set their own volume, this only looks after a single `MusicCue::Track`
+pub fn update_track_volume(
+    music_director: Res<MusicDirector>,
+    game_options: Res<GameOptions>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    if !game_options.is_changed() && !music_director.is_changed() {
+        return;
+    }
+
+    let Some(instance) = music_director
+        .instance
+        .as_ref()
+        .and_then(|instance| audio_instances.get_mut(instance))
+    else {
+        return;
+    };
+
+    instance.set_volume(
+        game_options.channel_volume(VolumeSetting::Music) * music_director.volume_scale(),
+        AudioTween::linear(Duration::from_millis(DUCK_MILLIS)),
+    );
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+use bevy_aseprite_ultra::prelude::*;
+use bevy_kira_audio::{AudioSource, prelude::*};
+use bevy_rapier2d::prelude::Velocity;
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    GameOptions, load_data_file,
+    sounds::{SfxChannel, VolumeSetting},
+};
+
+const SOUND_EFFECTS_PATH: &str = "data/sound_effects.ron";
+// Sounds further away from Thunwa than this can't be heard
+pub const SFX_RADIUS: f32 = 640.0;
+// Long enough for any one shot to finish before its emitter goes away
+const ONE_SHOT_LIFETIME: f32 = 3.0;
+// Below this speed a walk animation doesn't make any footsteps
+const FOOTSTEP_MIN_SPEED: f32 = 10.0;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    ThunwaFootstep,
+    ZombieFootstep,
+    ZombieGroan,
+    ZombieAttack,
+    DoorOpen,
+    BulletImpact,
+    FleshImpact,
+}
+
+// Every variant of each effect, one is picked at random whenever it plays
+#[derive(Resource, Default, Debug)]
+pub struct SoundEffects(pub HashMap<SoundEffect, Vec<Handle<AudioSource>>>);
+
+#[derive(Debug, Clone, Copy)]
+pub enum SoundSource {
+    // Needs a `SpatialAudioEmitter`, the sound follows the entity around
+    Entity(Entity),
+    // Plays from a short lived emitter, e.g. for a bullet hitting a wall
+    Position(Vec2),
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlaySoundEvent {
+    pub effect: SoundEffect,
+    pub source: SoundSource,
+}
+
+impl PlaySoundEvent {
+    pub fn at_entity(effect: SoundEffect, entity: Entity) -> Self {
+        Self {
+            effect,
+            source: SoundSource::Entity(entity),
+        }
+    }
+
+    pub fn at_position(effect: SoundEffect, position: Vec2) -> Self {
+        Self {
+            effect,
+            source: SoundSource::Position(position),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct OneShotEmitter(pub Timer);
+
+// Plays a footstep every few frames of the walk animation
+#[derive(Component, Debug)]
+pub struct Footsteps {
+    pub effect: SoundEffect,
+    pub frames_per_step: u32,
+    last_frame: Option<u16>,
+    frames: u32,
+}
+
+impl Footsteps {
+    pub fn new(effect: SoundEffect, frames_per_step: u32) -> Self {
+        Self {
+            effect,
+            frames_per_step,
+            last_frame: None,
+            frames: 0,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct ZombieGroans {
+    pub timer: Timer,
+}
+
+impl ZombieGroans {
+    // Zombies groan every 4 to 10 seconds, spread out so they don't all go at once
+    pub fn new() -> Self {
+        Self {
+            timer: Timer::from_seconds(rand::rng().random_range(4.0..10.0), TimerMode::Once),
+        }
+    }
+}
+
+impl Default for ZombieGroans {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn load_sound_effects(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let definitions: HashMap<SoundEffect, Vec<String>> =
+        load_data_file(SOUND_EFFECTS_PATH).unwrap_or_default();
+
+    let effects = definitions
+        .into_iter()
+        .map(|(effect, paths)| {
+            let variants = paths
+                .into_iter()
+                .map(|path| asset_server.load(path))
+                .collect();
+
+            (effect, variants)
+        })
+        .collect();
+
+    commands.insert_resource(SoundEffects(effects));
+}
+
+// A play command for a source that never loads would be retried forever, so failed variants are
+// dropped and the effect plays one of the others
+pub fn drop_failed_sound_effects(
+    mut failed_events: EventReader<AssetLoadFailedEvent<AudioSource>>,
+    mut sound_effects: ResMut<SoundEffects>,
+) {
+    for event in failed_events.read() {
+        for variants in sound_effects.0.values_mut() {
+            if variants.iter().any(|variant| variant.id() == event.id) {
+                println!("❌ Failed to load sound effect {}", event.path);
+                variants.retain(|variant| variant.id() != event.id);
+            }
+        }
+    }
+}
+
+pub fn play_sound_effects(
+    mut commands: Commands,
+    mut sound_events: EventReader<PlaySoundEvent>,
+    sound_effects: Res<SoundEffects>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+    mut emitter_query: Query<&mut SpatialAudioEmitter>,
+) {
+    let mut rng = rand::rng();
+
+    for event in sound_events.read() {
+        let Some(source) = sound_effects
+            .0
+            .get(&event.effect)
+            .and_then(|variants| variants.choose(&mut rng))
+        else {
+            continue;
+        };
+
+        // A slightly different pitch each time keeps repeated sounds from grating
+        let instance = sfx_channel
+            .play(source.clone())
+            .with_playback_rate(rng.random_range(0.92..1.08))
+            .handle();
+
+        match event.source {
+            SoundSource::Entity(entity) => {
+                if let Ok(mut emitter) = emitter_query.get_mut(entity) {
+                    emitter.instances.push(instance);
+                }
+            }
+            SoundSource::Position(position) => {
+                commands.spawn((
+                    OneShotEmitter(Timer::from_seconds(ONE_SHOT_LIFETIME, TimerMode::Once)),
+                    SpatialAudioEmitter {
+                        instances: vec![instance],
+                    },
+                    Transform::from_translation(position.extend(0.0)),
+                ));
+            }
+        }
+    }
+}
+
+// Fades and pans every emitter's sounds by where they are relative to Thunwa. Kira's own
+// `SpatialAudioPlugin` sets the volume from the distance alone, which threw away the SFX volume,
+// so this takes its place and runs after the channel commands that would otherwise override it
+pub fn spatialize_sound_effects(
+    game_options: Res<GameOptions>,
+    receiver_query: Query<&GlobalTransform, With<SpatialAudioReceiver>>,
+    emitter_query: Query<(&GlobalTransform, &SpatialAudioEmitter)>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let Ok(receiver_transform) = receiver_query.single() else {
+        return;
+    };
+
+    let sfx_volume = game_options.channel_volume(VolumeSetting::Sfx);
+
+    for (emitter_transform, emitter) in emitter_query.iter() {
+        let offset = emitter_transform.translation().xy() - receiver_transform.translation().xy();
+        let attenuation = (1.0 - offset.length() / SFX_RADIUS).clamp(0.0, 1.0);
+        // 0.0 is all the way left and 1.0 all the way right
+        let panning = (offset.normalize_or_zero().x + 1.0) / 2.0;
+
+        for instance in emitter.instances.iter() {
+            if let Some(instance) = audio_instances.get_mut(instance) {
+                instance.set_volume(attenuation as f64 * sfx_volume, AudioTween::default());
+                instance.set_panning(panning as f64, AudioTween::default());
+            }
+        }
+    }
+}
+
+// Emitters keep a handle to every sound they played, drop the ones that are done
+pub fn prune_finished_sounds(
+    mut emitter_query: Query<&mut SpatialAudioEmitter>,
+    audio_instances: Res<Assets<AudioInstance>>,
+) {
+    for mut emitter in emitter_query.iter_mut() {
+        let finished = |instance: &Handle<AudioInstance>| {
+            audio_instances
+                .get(instance)
+                .is_some_and(|instance| instance.state() == PlaybackState::Stopped)
+        };
+
+        if emitter.instances.iter().any(finished) {
+            emitter.instances.retain(|instance| !finished(instance));
+        }
+    }
+}
+
+pub fn despawn_one_shot_emitters(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut emitter_query: Query<(Entity, &mut OneShotEmitter)>,
+) {
+    for (entity, mut one_shot) in emitter_query.iter_mut() {
+        if one_shot.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn play_footsteps(
+    mut footsteps_query: Query<(Entity, &mut Footsteps, &AnimationState, &Velocity)>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+) {
+    for (entity, mut footsteps, animation_state, velocity) in footsteps_query.iter_mut() {
+        if footsteps.last_frame == Some(animation_state.current_frame) {
+            continue;
+        }
+
+        footsteps.last_frame = Some(animation_state.current_frame);
+
+        if velocity.linvel.length() < FOOTSTEP_MIN_SPEED {
+            footsteps.frames = 0;
+            continue;
+        }
+
+        footsteps.frames += 1;
+
+        if footsteps.frames % footsteps.frames_per_step == 0 {
+            sound_events.write(PlaySoundEvent::at_entity(footsteps.effect, entity));
+        }
+    }
+}
+
+pub fn zombie_groans(
+    time: Res<Time>,
+    mut groans_query: Query<(Entity, &mut ZombieGroans)>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+) {
+    for (entity, mut groans) in groans_query.iter_mut() {
+        if !groans.timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        sound_events.write(PlaySoundEvent::at_entity(SoundEffect::ZombieGroan, entity));
+        *groans = ZombieGroans::new();
+    }
+}
+
+pub fn despawn_one_shots_on_exit(
+    mut commands: Commands,
+    emitter_query: Query<Entity, With<OneShotEmitter>>,
+) {
+    for entity in emitter_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -4,8 +4,22 @@ use bevy_light_2d::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
-    characters::thunwa::Thunwa,
-    terrains::{DynamicsZOrder, GRID_SIZE, MAP_SIZE, TILE_SIZE},
+    characters::{sanity::GoreSpot, thunwa::Thunwa},
+    cutscene::CutsceneTrigger,
+    dialogue::DialogueTrigger,
+    inventory::pickup::{Pickup, pickup_sprite},
+    lighting::LightFlicker,
+    sounds::sfx::{PlaySoundEvent, SoundEffect},
+    terrains::{
+        DoorOpenedEvent, DynamicsZOrder, GRID_SIZE, MAP_SIZE, SceneAmbientLight, SceneBounds,
+        SceneZone, TILE_SIZE,
+    },
+};
+
+// Night time outside the condo, only the lamps and the flashlight light the way
+const AMBIENT_LIGHT: SceneAmbientLight = SceneAmbientLight {
+    color: Color::srgb(0.6, 0.65, 0.85),
+    brightness: 0.15,
 };
 
 #[derive(Component)]
@@ -28,6 +42,11 @@ enum TextureIndex {
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CondoClosedDoorEntering(pub bool);
 
+pub const CONDO_ENTRANCE_DOOR: &str = "condo_entrance";
+pub const CONDO_FORECOURT_ZONE: &str = "condo_forecourt";
+// How close Thunwa has to be to open the door with E
+const DOOR_INTERACT_RADIUS: f32 = GRID_SIZE * 2.5;
+
 pub fn draw_terrain(mut commands: Commands, asset_server: Res<AssetServer>) {
     let scene_image = asset_server.load("tileset/condo/entering/scene.png");
     let texture_handle: Handle<Image> = asset_server.load("tileset/condo/entering/tiles_1.png");
@@ -41,22 +60,16 @@ pub fn draw_terrain(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn((
             CondoEnteringScene,
+            AMBIENT_LIGHT,
+            SceneBounds {
+                rect: Rect::from_center_size(
+                    Vec2::ZERO,
+                    Vec2::new(MAP_SIZE.x as f32 * GRID_SIZE, MAP_SIZE.y as f32 * GRID_SIZE),
+                ),
+            },
             Sprite::from_image(scene_image),
             Transform::from_xyz(0.0, 0.0, 0.0),
         ))
-        .with_children(|parent| {
-            // Draw Terrains
-            parent
-                .spawn(Sprite {
-                    custom_size: Some(Vec2::new(
-                        MAP_SIZE.x as f32 * GRID_SIZE,
-                        MAP_SIZE.y as f32 * GRID_SIZE,
-                    )),
-                    color: Color::srgba(0.0, 0.0, 0.0, 0.8),
-                    ..Default::default()
-                })
-                .insert(Transform::from_xyz(0.0, 0.0, 1.0));
-        })
         .with_children(|parent| {
             // Draw the collider
             // Map bounds
@@ -233,6 +246,17 @@ pub fn draw_terrain(mut commands: Commands, asset_server: Res<AssetServer>) {
                     .id();
                 tile_storage.set(&tile_pos, tile_entity);
             }
+
+            // Witnessing the stains shakes Thunwa, centered on the splatter
+            parent.spawn((
+                GoreSpot::new(GRID_SIZE * 4., 15.),
+                DialogueTrigger::new("bloodstains", GRID_SIZE * 4.),
+                Transform::from_xyz(
+                    -(MAP_SIZE.x as f32 * GRID_SIZE) / 2. + GRID_SIZE * 2.5,
+                    (MAP_SIZE.y as f32 * GRID_SIZE) / 2. - GRID_SIZE * 5.,
+                    0.,
+                ),
+            ));
         })
         .with_children(|parent_1| {
             // Draw trees
@@ -280,7 +304,14 @@ pub fn draw_terrain(mut commands: Commands, asset_server: Res<AssetServer>) {
             let x_collider = 10. * (GRID_SIZE / 16.) / 2.;
             let y_collider = 2. * (GRID_SIZE / 16.) / 2.;
 
-            for (x, y) in lamp_positions {
+            for (index, (x, y)) in lamp_positions.into_iter().enumerate() {
+                // The middle lamp on the right is failing, the rest just hum
+                let flicker = if index == 2 {
+                    LightFlicker::random(0.5, 2.5, 0.1).with_dark_chance(0.25)
+                } else {
+                    LightFlicker::random(1.8, 2.2, 0.15)
+                };
+
                 parent_1
                     .spawn((
                         DynamicsZOrder,
@@ -298,11 +329,42 @@ pub fn draw_terrain(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 intensity: 2.0,
                                 radius: 80.0,
                                 color: Color::WHITE,
+                                cast_shadows: true,
                                 ..Default::default()
                             })
+                            .insert(flicker)
                             .insert(Transform::from_xyz(0.0, GRID_SIZE, 0.0));
                     });
             }
+        })
+        .with_children(|parent| {
+            // Draw pickups
+            let pickups = [
+                ("medkit", 1, (-GRID_SIZE * 8., -GRID_SIZE * 5.)),
+                ("painkillers", 2, (GRID_SIZE * 10., -GRID_SIZE * 2.)),
+                ("pistol_ammo", 24, (-GRID_SIZE * 14., -GRID_SIZE * 8.)),
+                ("pistol_ammo", 12, (GRID_SIZE * 15., -GRID_SIZE * 4.)),
+                ("battery", 1, (GRID_SIZE * 2., -GRID_SIZE * 1.)),
+            ];
+
+            for (item, count, (x, y)) in pickups {
+                parent.spawn((
+                    Pickup::new(item, count),
+                    pickup_sprite(item),
+                    Transform::from_xyz(x, y, 12.),
+                ));
+            }
+        })
+        .with_children(|parent| {
+            // The paved area right in front of the entrance, something is waiting there
+            parent.spawn((
+                SceneZone::new(
+                    CONDO_FORECOURT_ZONE,
+                    Vec2::new(GRID_SIZE * 4., GRID_SIZE * 2.),
+                ),
+                CutsceneTrigger::new("forecourt_ambush", GRID_SIZE * 2.),
+                Transform::from_xyz(-(17. * GRID_SIZE), 4. * GRID_SIZE, 0.),
+            ));
         });
 
     let grid_size = TILE_SIZE.into();
@@ -346,3 +408,52 @@ pub fn update_z_order(
         }
     }
 }
+
+pub fn open_condo_door(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut door_query: Query<(
+        Entity,
+        &mut CondoClosedDoorEntering,
+        &GlobalTransform,
+        &mut Visibility,
+    )>,
+    thunwa_query: Query<&Transform, With<Thunwa>>,
+    mut door_events: EventWriter<DoorOpenedEvent>,
+    mut sound_events: EventWriter<PlaySoundEvent>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyE) {
+        return;
+    }
+
+    let Ok(thunwa_transform) = thunwa_query.single() else {
+        return;
+    };
+
+    for (entity, mut door, door_transform, mut visibility) in door_query.iter_mut() {
+        if !door.0 {
+            continue; // Already open
+        }
+
+        let distance =
+            (door_transform.translation().xy() - thunwa_transform.translation.xy()).length();
+        if distance > DOOR_INTERACT_RADIUS {
+            continue;
+        }
+
+        // No open door sprite yet, the doorway is just left clear
+        door.0 = false;
+        *visibility = Visibility::Hidden;
+        commands
+            .entity(entity)
+            .remove::<(Collider, LightOccluder2d)>();
+
+        door_events.write(DoorOpenedEvent {
+            door: CONDO_ENTRANCE_DOOR.to_string(),
+        });
+        sound_events.write(PlaySoundEvent::at_position(
+            SoundEffect::DoorOpen,
+            door_transform.translation().xy(),
+        ));
+    }
+}
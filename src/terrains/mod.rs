@@ -1,5 +1,9 @@
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
+use bevy_light_2d::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{camera::PlayerCamera, characters::thunwa::Thunwa};
 
 // Notes
 // X: 0 Is left
@@ -15,4 +19,129 @@ pub(crate) const MAP_SIZE: TilemapSize = TilemapSize { x: 40, y: 20 };
 #[derive(Component)]
 pub struct DynamicsZOrder;
 
+// Ambient light level for a scene, put on the scene root and copied onto the player camera
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SceneAmbientLight {
+    pub color: Color,
+    pub brightness: f32,
+}
+
+// World-space area the player camera may show, put on the scene root
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SceneBounds {
+    pub rect: Rect,
+}
+
+// Named area that reports when Thunwa walks into it, e.g. for objectives
+#[derive(Component, Debug, Clone)]
+pub struct SceneZone {
+    pub id: String,
+    pub half_size: Vec2,
+    pub occupied: bool,
+}
+
+impl SceneZone {
+    pub fn new(id: &str, half_size: Vec2) -> Self {
+        Self {
+            id: id.to_string(),
+            half_size,
+            occupied: false,
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct ZoneEnteredEvent {
+    pub zone: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct DoorOpenedEvent {
+    pub door: String,
+}
+
+// Level geometry is any static collider without collision groups, characters and projectiles
+// always set their own groups
+type NewLevelColliderFilter = (
+    Added<Collider>,
+    Without<RigidBody>,
+    Without<Sensor>,
+    Without<CollisionGroups>,
+    Without<LightOccluder2d>,
+);
+
+pub fn generate_light_occluders(
+    mut commands: Commands,
+    collider_query: Query<(Entity, &Collider), NewLevelColliderFilter>,
+) {
+    for (entity, collider) in collider_query.iter() {
+        let Some(cuboid) = collider.as_cuboid() else {
+            continue;
+        };
+
+        commands.entity(entity).insert(LightOccluder2d {
+            shape: LightOccluder2dShape::Rectangle {
+                half_size: cuboid.half_extents(),
+            },
+        });
+    }
+}
+
+pub fn apply_scene_ambient_light(
+    mut commands: Commands,
+    scene_query: Query<&SceneAmbientLight>,
+    mut camera_query: Query<(Entity, Option<&mut AmbientLight2d>), With<PlayerCamera>>,
+) {
+    let Ok(scene_ambient) = scene_query.single() else {
+        return;
+    };
+
+    for (entity, ambient_light) in camera_query.iter_mut() {
+        match ambient_light {
+            Some(mut ambient_light) => {
+                if ambient_light.color != scene_ambient.color
+                    || ambient_light.brightness != scene_ambient.brightness
+                {
+                    ambient_light.color = scene_ambient.color;
+                    ambient_light.brightness = scene_ambient.brightness;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(AmbientLight2d {
+                    color: scene_ambient.color,
+                    brightness: scene_ambient.brightness,
+                });
+            }
+        }
+    }
+}
+
+pub fn detect_zone_entry(
+    mut zone_query: Query<(&mut SceneZone, &GlobalTransform)>,
+    thunwa_query: Query<&Transform, With<Thunwa>>,
+    mut zone_events: EventWriter<ZoneEnteredEvent>,
+) {
+    let Ok(thunwa_transform) = thunwa_query.single() else {
+        return;
+    };
+
+    let thunwa_pos = thunwa_transform.translation.xy();
+
+    for (mut zone, zone_transform) in zone_query.iter_mut() {
+        let offset = (thunwa_pos - zone_transform.translation().xy()).abs();
+        let inside = offset.x <= zone.half_size.x && offset.y <= zone.half_size.y;
+
+        // Only the moment of walking in counts, not standing inside
+        if inside && !zone.occupied {
+            zone_events.write(ZoneEnteredEvent {
+                zone: zone.id.clone(),
+            });
+        }
+
+        if zone.occupied != inside {
+            zone.occupied = inside;
+        }
+    }
+}
+
 pub mod condo_entering;
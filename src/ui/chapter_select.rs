@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+
+use crate::{
+    GameState, MainMenuState, PauseState,
+    chapters::{ChapterDefinitions, ChapterProgress, CurrentChapter},
+    localization::{Localization, LocalizedText},
+};
+
+#[derive(Component)]
+pub struct ChapterSelectUI;
+
+#[derive(Component)]
+pub struct ChapterButton(pub String);
+
+#[derive(Component)]
+pub struct ChapterSelectBackButton;
+
+pub fn spawn_chapter_select(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    definitions: Res<ChapterDefinitions>,
+    progress: Res<ChapterProgress>,
+) {
+    let font = localization.font();
+    let font_bold = localization.font_bold();
+
+    commands
+        .spawn((
+            ChapterSelectUI,
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceEvenly,
+                ..Default::default()
+            },
+            BackgroundColor(Color::NONE),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText("chapter_select.title"),
+                Text::default(),
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextFont {
+                    font: font_bold.clone(),
+                    font_size: 94.,
+                    ..Default::default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(24.),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for chapter in definitions.0.iter() {
+                        let unlocked = progress.is_unlocked(chapter);
+                        let title = localization
+                            .text_or(&format!("chapter.{}.title", chapter.id), &chapter.title);
+                        let label = if !unlocked {
+                            localization.format("chapter_select.locked", &[("title", title)])
+                        } else if progress.is_completed(&chapter.id) {
+                            localization.format("chapter_select.completed", &[("title", title)])
+                        } else {
+                            title.to_string()
+                        };
+
+                        let mut button = parent.spawn((
+                            Node {
+                                width: Val::Px(820.),
+                                height: Val::Px(88.),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(2.)),
+                                ..Default::default()
+                            },
+                            BorderColor(Color::WHITE.with_alpha(if unlocked { 1.0 } else { 0.3 })),
+                            BackgroundColor(Color::WHITE.with_alpha(0.0)),
+                        ));
+
+                        // Locked chapters are shown but can't be picked
+                        if unlocked {
+                            button.insert((Button, ChapterButton(chapter.id.clone())));
+                        }
+
+                        button.with_children(|parent| {
+                            parent.spawn((
+                                Text::new(label),
+                                TextColor(Color::WHITE.with_alpha(if unlocked {
+                                    1.0
+                                } else {
+                                    0.4
+                                })),
+                                TextLayout::new_with_justify(JustifyText::Center),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 40.,
+                                    ..Default::default()
+                                },
+                            ));
+                        });
+                    }
+                });
+
+            parent
+                .spawn((
+                    Button,
+                    ChapterSelectBackButton,
+                    Node {
+                        width: Val::Px(502.),
+                        height: Val::Px(88.),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        border: UiRect::all(Val::Px(2.)),
+                        ..Default::default()
+                    },
+                    BorderColor(Color::WHITE),
+                    BackgroundColor(Color::WHITE.with_alpha(0.0)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        LocalizedText("chapter_select.back"),
+                        Text::default(),
+                        TextColor(Color::WHITE),
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 48.,
+                            ..Default::default()
+                        },
+                    ));
+                });
+        });
+}
+
+pub fn despawn_chapter_select(mut commands: Commands, query: Query<Entity, With<ChapterSelectUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn chapter_button_handler(
+    mut commands: Commands,
+    chapter_query: Query<(&Interaction, &ChapterButton), Changed<Interaction>>,
+    back_query: Query<&Interaction, (Changed<Interaction>, With<ChapterSelectBackButton>)>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_main_menu_state: ResMut<NextState<MainMenuState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape)
+        || back_query
+            .iter()
+            .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        next_main_menu_state.set(MainMenuState::MainMenu);
+        return;
+    }
+
+    for (interaction, chapter) in chapter_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        commands.insert_resource(CurrentChapter::new(&chapter.0));
+        next_game_state.set(GameState::InGame);
+        next_pause_state.set(PauseState::InGame);
+        next_main_menu_state.set(MainMenuState::None);
+        return;
+    }
+}
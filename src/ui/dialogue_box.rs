@@ -0,0 +1,251 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_aseprite_ultra::prelude::*;
+
+use crate::{
+    dialogue::{ActiveDialogue, DialogueLibrary},
+    localization::Localization,
+};
+
+const SELECTED_CHOICE_COLOR: Color = Color::srgb(163. / 255., 32. / 255., 32. / 255.);
+
+#[derive(Component)]
+pub struct DialogueBox;
+
+#[derive(Component)]
+pub struct DialoguePortrait;
+
+#[derive(Component)]
+pub struct DialogueSpeakerText;
+
+#[derive(Component)]
+pub struct DialogueLineText;
+
+#[derive(Component)]
+pub struct DialogueChoiceList;
+
+#[derive(Component)]
+pub struct DialogueChoiceText(pub usize);
+
+pub fn spawn_dialogue_box(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    dialogue_box_query: Query<(), With<DialogueBox>>,
+) {
+    if !dialogue_box_query.is_empty() {
+        return;
+    }
+
+    let font = localization.font();
+    let font_bold = localization.font_bold();
+
+    commands
+        .spawn((
+            DialogueBox,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(32.),
+                left: Val::Percent(10.),
+                width: Val::Percent(80.),
+                min_height: Val::Px(220.),
+                padding: UiRect::all(Val::Px(24.)),
+                column_gap: Val::Px(24.),
+                border: UiRect::all(Val::Px(2.)),
+                flex_direction: FlexDirection::Row,
+                ..Default::default()
+            },
+            BorderColor(Color::WHITE),
+            BackgroundColor(Color::BLACK.with_alpha(0.85)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                DialoguePortrait,
+                Node {
+                    width: Val::Px(160.),
+                    height: Val::Px(160.),
+                    flex_shrink: 0.,
+                    border: UiRect::all(Val::Px(2.)),
+                    ..Default::default()
+                },
+                BorderColor(Color::WHITE),
+                ImageNode::default(),
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    flex_grow: 1.,
+                    row_gap: Val::Px(12.),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        DialogueSpeakerText,
+                        Text::new(""),
+                        TextColor(Color::WHITE),
+                        TextFont {
+                            font: font_bold.clone(),
+                            font_size: 32.,
+                            ..Default::default()
+                        },
+                    ));
+
+                    parent.spawn((
+                        DialogueLineText,
+                        Text::new(""),
+                        TextColor(Color::WHITE),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 28.,
+                            ..Default::default()
+                        },
+                    ));
+
+                    parent.spawn((
+                        DialogueChoiceList,
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(6.),
+                            margin: UiRect::top(Val::Px(8.)),
+                            ..Default::default()
+                        },
+                    ));
+                });
+        });
+}
+
+pub fn despawn_dialogue_box(
+    mut commands: Commands,
+    dialogue_box_query: Query<Entity, With<DialogueBox>>,
+) {
+    for entity in dialogue_box_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+type ChoiceListFilter = (With<DialogueChoiceList>, Without<DialoguePortrait>);
+
+// The parts of the dialogue box that follow the active node
+#[derive(SystemParam)]
+pub struct DialogueBoxWidgets<'w, 's> {
+    speaker:
+        Query<'w, 's, &'static mut Text, (With<DialogueSpeakerText>, Without<DialogueLineText>)>,
+    line: Query<'w, 's, &'static mut Text, (With<DialogueLineText>, Without<DialogueSpeakerText>)>,
+    portrait: Query<'w, 's, (Entity, &'static mut Node), With<DialoguePortrait>>,
+    choice_list: Query<'w, 's, (Entity, &'static mut Node), ChoiceListFilter>,
+    choices: Query<'w, 's, (&'static DialogueChoiceText, &'static mut TextColor)>,
+}
+
+pub fn update_dialogue_box(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    library: Res<DialogueLibrary>,
+    active_dialogue: Res<ActiveDialogue>,
+    mut last_node: Local<Option<(String, String)>>,
+    mut widgets: DialogueBoxWidgets,
+) {
+    if !active_dialogue.is_changed() {
+        return;
+    }
+
+    let Some(node) = library.node(&active_dialogue.dialogue, &active_dialogue.node) else {
+        return;
+    };
+
+    let key = (
+        active_dialogue.dialogue.clone(),
+        active_dialogue.node.clone(),
+    );
+    // A fresh box starts out empty even when the same node is shown again
+    let node_changed = active_dialogue.is_added() || last_node.as_ref() != Some(&key);
+    let line = active_dialogue
+        .line(&library, &localization)
+        .unwrap_or_default();
+    let line_done = active_dialogue.revealed >= line.chars().count();
+
+    if let Ok(mut line_text) = widgets.line.single_mut() {
+        line_text.0 = line.chars().take(active_dialogue.revealed).collect();
+    }
+
+    if node_changed {
+        *last_node = Some(key);
+        let speaker = library.speakers.get(&node.speaker);
+
+        if let Ok(mut speaker_text) = widgets.speaker.single_mut() {
+            speaker_text.0 = speaker
+                .map(|speaker| {
+                    localization.text_or(&format!("speaker.{}", node.speaker), &speaker.name)
+                })
+                .unwrap_or(node.speaker.as_str())
+                .to_string();
+        }
+
+        if let Ok((portrait_entity, mut portrait_node)) = widgets.portrait.single_mut() {
+            match speaker.and_then(|speaker| speaker.portrait.as_ref().map(|path| (speaker, path)))
+            {
+                Some((speaker, path)) => {
+                    portrait_node.display = Display::Flex;
+                    commands.entity(portrait_entity).insert(AseAnimation {
+                        aseprite: asset_server.load(path.clone()),
+                        animation: Animation::tag(&speaker.portrait_tag),
+                    });
+                }
+                None => {
+                    portrait_node.display = Display::None;
+                }
+            }
+        }
+
+        // Choices are rebuilt for every node
+        if let Ok((choice_list, _)) = widgets.choice_list.single() {
+            let font = localization.font();
+
+            commands
+                .entity(choice_list)
+                .despawn_related::<Children>()
+                .with_children(|parent| {
+                    for (position, index) in active_dialogue.choices.iter().enumerate() {
+                        let choice = localization.text_or(
+                            &format!(
+                                "dialogue.{}.{}.choice_{index}",
+                                active_dialogue.dialogue, active_dialogue.node
+                            ),
+                            &node.choices[*index].text,
+                        );
+
+                        parent.spawn((
+                            DialogueChoiceText(position),
+                            Text::new(format!("> {choice}")),
+                            TextColor(if position == active_dialogue.selected {
+                                SELECTED_CHOICE_COLOR
+                            } else {
+                                Color::WHITE
+                            }),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 26.,
+                                ..Default::default()
+                            },
+                        ));
+                    }
+                });
+        }
+    }
+
+    for (choice, mut color) in widgets.choices.iter_mut() {
+        color.0 = if choice.0 == active_dialogue.selected {
+            SELECTED_CHOICE_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+
+    // The choices only show up once the typewriter is done with the line
+    if let Ok((_, mut choice_list_node)) = widgets.choice_list.single_mut() {
+        choice_list_node.display = if line_done {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
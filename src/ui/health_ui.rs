@@ -1,6 +1,17 @@
 use bevy::prelude::*;
+use rand::prelude::*;
 
-use crate::characters::thunwa::ThunwaHealth;
+use crate::{
+    characters::{
+        flashlight::Flashlight,
+        health::Health,
+        sanity::ThunwaSanity,
+        thunwa::{Thunwa, ThunwaStamina},
+        weapon::Weapon,
+    },
+    inventory::Inventory,
+    localization::Localization,
+};
 
 #[derive(Component)]
 pub struct HealthBar;
@@ -8,12 +19,27 @@ pub struct HealthBar;
 #[derive(Component)]
 pub struct HealthFill;
 
+#[derive(Component)]
+pub struct StaminaBar;
+
+#[derive(Component)]
+pub struct StaminaFill;
+
+#[derive(Component)]
+pub struct AmmoText;
+
+#[derive(Component)]
+pub struct BatteryText;
+
+#[derive(Component)]
+pub struct SanityOverlay;
+
 #[derive(Resource)]
 pub struct HealthFlickerTimer {
     pub timer: Timer,
     pub is_flickering: bool,
     pub flicker_count: i32,
-    pub initialized: bool,
+    pub last_health: Option<f32>,
 }
 
 impl Default for HealthFlickerTimer {
@@ -22,12 +48,26 @@ impl Default for HealthFlickerTimer {
             timer: Timer::from_seconds(0.1, TimerMode::Repeating),
             is_flickering: false,
             flicker_count: 0,
-            initialized: false,
+            last_health: None,
         }
     }
 }
 
-pub fn spawn_health_ui(mut commands: Commands) {
+pub fn spawn_health_ui(mut commands: Commands, localization: Res<Localization>) {
+    let font = localization.font();
+
+    // Spawned first so it stays underneath the bars
+    commands.spawn((
+        SanityOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            ..Default::default()
+        },
+        BackgroundColor(Color::NONE),
+    ));
+
     commands
         .spawn((
             HealthBar,
@@ -79,48 +119,139 @@ pub fn spawn_health_ui(mut commands: Commands) {
                     ));
                 });
         });
+
+    commands
+        .spawn((
+            StaminaBar,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(76.0),
+                left: Val::Px(20.0),
+                width: Val::Px(400.0),
+                height: Val::Px(16.0),
+                border: UiRect {
+                    left: Val::Px(2.0),
+                    right: Val::Px(2.0),
+                    top: Val::Px(2.0),
+                    bottom: Val::Px(2.0),
+                },
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.8)),
+            BorderColor(Color::WHITE),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                StaminaFill,
+                Node {
+                    width: Val::Px(396.0),
+                    height: Val::Px(12.0),
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    ..Default::default()
+                },
+                BackgroundColor(Color::srgb(0.85, 0.85, 0.85)),
+            ));
+        });
+
+    commands.spawn((
+        AmmoText,
+        Text::new(""),
+        TextColor(Color::WHITE),
+        TextFont {
+            font: font.clone(),
+            font_size: 28.,
+            ..Default::default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(104.0),
+            left: Val::Px(20.0),
+            ..Default::default()
+        },
+    ));
+
+    commands.spawn((
+        BatteryText,
+        Text::new(""),
+        TextColor(Color::WHITE),
+        TextFont {
+            font,
+            font_size: 28.,
+            ..Default::default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(140.0),
+            left: Val::Px(20.0),
+            ..Default::default()
+        },
+    ));
 }
 
-pub fn despawn_health_ui(mut commands: Commands, health_bar_query: Query<Entity, With<HealthBar>>) {
+// Every root node the HUD spawns
+type HudRootFilter = Or<(
+    With<HealthBar>,
+    With<StaminaBar>,
+    With<AmmoText>,
+    With<BatteryText>,
+    With<SanityOverlay>,
+)>;
+
+pub fn despawn_health_ui(
+    mut commands: Commands,
+    health_bar_query: Query<Entity, HudRootFilter>,
+    mut flicker_timer: ResMut<HealthFlickerTimer>,
+) {
     for entity in health_bar_query.iter() {
         commands.entity(entity).despawn();
     }
+
+    *flicker_timer = HealthFlickerTimer::default();
 }
 
 pub fn update_health_ui(
-    thunwa_health: Res<ThunwaHealth>,
+    thunwa_query: Query<Ref<Health>, With<Thunwa>>,
     mut health_fill_query: Query<&mut Node, With<HealthFill>>,
     mut flicker_timer: ResMut<HealthFlickerTimer>,
 ) {
-    // Skip flicker on first initialization
-    if !flicker_timer.initialized {
-        flicker_timer.initialized = true;
+    let Ok(thunwa_health) = thunwa_query.single() else {
+        return;
+    };
+
+    if !thunwa_health.is_changed() {
         return;
     }
 
-    if thunwa_health.is_changed() && thunwa_health.current < thunwa_health.max {
-        // Start flicker when health changes (indicating damage)
+    // Only flicker when health went down, skipping the first reading after spawn
+    if let Some(last_health) = flicker_timer.last_health
+        && thunwa_health.current < last_health
+    {
         flicker_timer.is_flickering = true;
         flicker_timer.timer.reset();
         flicker_timer.flicker_count = 0;
+    }
+    flicker_timer.last_health = Some(thunwa_health.current);
 
-        // Update health bar fill width
-        if let Ok(mut fill_node) = health_fill_query.single_mut() {
-            let health_percentage = thunwa_health.current / thunwa_health.max;
-            fill_node.width = Val::Px(396.0 * health_percentage);
-        }
+    // Update health bar fill width
+    if let Ok(mut fill_node) = health_fill_query.single_mut() {
+        fill_node.width = Val::Px(396.0 * thunwa_health.fraction());
     }
 }
 
 pub fn update_health_bar_color(
-    thunwa_health: Res<ThunwaHealth>,
+    thunwa_query: Query<&Health, With<Thunwa>>,
     mut health_fill_query: Query<&mut BackgroundColor, With<HealthFill>>,
     flicker_timer: Res<HealthFlickerTimer>,
 ) {
+    let Ok(thunwa_health) = thunwa_query.single() else {
+        return;
+    };
+
     // Only update if not flickering (flicker handled separately)
     if !flicker_timer.is_flickering {
         if let Ok(mut bg_color) = health_fill_query.single_mut() {
-            let health_percentage = thunwa_health.current / thunwa_health.max;
+            let health_percentage = thunwa_health.fraction();
 
             // Normal color coding based on health percentage
             // Start color (> 80%), Yellow (50-80%), Red (< 50%)
@@ -139,8 +270,12 @@ pub fn update_health_flicker(
     time: Res<Time>,
     mut flicker_timer: ResMut<HealthFlickerTimer>,
     mut health_fill_query: Query<&mut BackgroundColor, With<HealthFill>>,
-    thunwa_health: Res<ThunwaHealth>,
+    thunwa_query: Query<&Health, With<Thunwa>>,
 ) {
+    let Ok(thunwa_health) = thunwa_query.single() else {
+        return;
+    };
+
     if flicker_timer.is_flickering {
         flicker_timer.timer.tick(time.delta());
 
@@ -154,7 +289,7 @@ pub fn update_health_flicker(
                 bg_color.0 = Color::srgb(0.8, 0.0, 0.0); // Red
             } else {
                 // Normal health-based color
-                let health_percentage = thunwa_health.current / thunwa_health.max;
+                let health_percentage = thunwa_health.fraction();
                 if health_percentage > 0.8 {
                     bg_color.0 = Color::srgba(99.0 / 255.0, 163.0 / 255.0, 97.0 / 255.0, 1.0);
                 } else if health_percentage > 0.5 {
@@ -173,3 +308,113 @@ pub fn update_health_flicker(
         }
     }
 }
+
+pub fn update_stamina_ui(
+    thunwa_stamina: Res<ThunwaStamina>,
+    mut stamina_fill_query: Query<(&mut Node, &mut BackgroundColor), With<StaminaFill>>,
+) {
+    if let Ok((mut fill_node, mut bg_color)) = stamina_fill_query.single_mut() {
+        fill_node.width = Val::Px(396.0 * thunwa_stamina.fraction());
+
+        // Dim the bar while Thunwa is catching his breath
+        bg_color.0 = if thunwa_stamina.exhausted {
+            Color::srgb(0.45, 0.35, 0.2)
+        } else {
+            Color::srgb(0.85, 0.85, 0.85)
+        };
+    }
+}
+
+pub fn update_ammo_ui(
+    inventory: Res<Inventory>,
+    localization: Res<Localization>,
+    weapon_query: Query<&Weapon, With<Thunwa>>,
+    mut ammo_text_query: Query<&mut Text, With<AmmoText>>,
+) {
+    let (Ok(weapon), Ok(mut text)) = (weapon_query.single(), ammo_text_query.single_mut()) else {
+        return;
+    };
+
+    let reserve = inventory.count(weapon.ammo_item).to_string();
+    let label = if weapon.reloading {
+        localization.format("hud.reloading", &[("reserve", &reserve)])
+    } else {
+        localization.format(
+            "hud.ammo",
+            &[
+                ("magazine", &weapon.magazine.to_string()),
+                ("reserve", &reserve),
+            ],
+        )
+    };
+
+    // Avoid touching the text every frame when nothing changed
+    if text.0 != label {
+        text.0 = label;
+    }
+}
+
+pub fn update_battery_ui(
+    localization: Res<Localization>,
+    flashlight_query: Query<&Flashlight, With<Thunwa>>,
+    mut battery_text_query: Query<(&mut Text, &mut TextColor), With<BatteryText>>,
+) {
+    let (Ok(flashlight), Ok((mut text, mut text_color))) =
+        (flashlight_query.single(), battery_text_query.single_mut())
+    else {
+        return;
+    };
+
+    let battery = format!("{:.0}", flashlight.battery * 100.0);
+    let label = if flashlight.on {
+        localization.format("hud.battery", &[("battery", &battery)])
+    } else {
+        localization.format("hud.battery_off", &[("battery", &battery)])
+    };
+
+    if text.0 != label {
+        text.0 = label;
+    }
+
+    // Warn in red once the flashlight starts flickering
+    let color = if flashlight.battery < 0.2 {
+        Color::srgb(0.8, 0.2, 0.1)
+    } else {
+        Color::WHITE
+    };
+
+    if text_color.0 != color {
+        text_color.0 = color;
+    }
+}
+
+pub fn update_sanity_ui(
+    time: Res<Time>,
+    thunwa_sanity: Res<ThunwaSanity>,
+    mut overlay_query: Query<&mut BackgroundColor, With<SanityOverlay>>,
+    mut health_bar_query: Query<&mut Node, With<HealthBar>>,
+) {
+    let distortion = thunwa_sanity.distortion();
+
+    // A faint bruise-colored tint that pulses like a heartbeat as sanity slips
+    if let Ok(mut bg_color) = overlay_query.single_mut() {
+        let pulse = (time.elapsed_secs() * 4.0).sin().abs();
+        let alpha = distortion * (0.15 + 0.1 * pulse);
+        bg_color.0 = Color::srgba(0.15, 0.0, 0.1, alpha);
+    }
+
+    // The HUD occasionally glitches out of place
+    if let Ok(mut node) = health_bar_query.single_mut() {
+        let mut rng = rand::rng();
+        let offset = if distortion > 0.0 && rng.random::<f32>() < distortion * 0.05 {
+            rng.random_range(-6.0..6.0)
+        } else {
+            0.0
+        };
+
+        let left = Val::Px(20.0 + offset);
+        if node.left != left {
+            node.left = left;
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+
+use crate::{
+    PauseOptionsState, PauseState,
+    inventory::{INVENTORY_SLOTS, Inventory, ItemDefinitions, UseItemEvent},
+    localization::{Localization, LocalizedText},
+};
+
+const INVENTORY_COLUMNS: usize = 4;
+
+#[derive(Component)]
+pub struct InventoryUI;
+
+#[derive(Component)]
+pub struct InventorySlotButton(pub usize);
+
+#[derive(Component)]
+pub struct InventorySlotText(pub usize);
+
+#[derive(Component)]
+pub struct InventoryDescriptionText;
+
+fn slot_label(
+    inventory: &Inventory,
+    definitions: &ItemDefinitions,
+    localization: &Localization,
+    slot: usize,
+) -> String {
+    match inventory.slots.get(slot) {
+        Some(Some(stack)) => {
+            let name = definitions
+                .get(&stack.item)
+                .map(|definition| {
+                    localization.text_or(&format!("item.{}.name", stack.item), &definition.name)
+                })
+                .unwrap_or(stack.item.as_str());
+
+            format!("{name}\nx{}", stack.count)
+        }
+        _ => String::new(),
+    }
+}
+
+pub fn spawn_inventory_menu(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    inventory: Res<Inventory>,
+    definitions: Res<ItemDefinitions>,
+) {
+    let font = localization.font();
+    let font_bold = localization.font_bold();
+
+    commands
+        .spawn((
+            InventoryUI,
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceEvenly,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText("inventory.title"),
+                Text::default(),
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextFont {
+                    font: font_bold.clone(),
+                    font_size: 94.,
+                    ..Default::default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(16.),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for row in 0..INVENTORY_SLOTS.div_ceil(INVENTORY_COLUMNS) {
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                column_gap: Val::Px(16.),
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                let first = row * INVENTORY_COLUMNS;
+                                let last = (first + INVENTORY_COLUMNS).min(INVENTORY_SLOTS);
+
+                                for slot in first..last {
+                                    parent
+                                        .spawn((
+                                            InventorySlotButton(slot),
+                                            Button,
+                                            Node {
+                                                width: Val::Px(220.),
+                                                height: Val::Px(120.),
+                                                justify_content: JustifyContent::Center,
+                                                align_items: AlignItems::Center,
+                                                border: UiRect::all(Val::Px(2.)),
+                                                ..Default::default()
+                                            },
+                                            BorderColor(Color::WHITE),
+                                            BackgroundColor(Color::WHITE.with_alpha(0.0)),
+                                        ))
+                                        .with_children(|parent| {
+                                            parent.spawn((
+                                                InventorySlotText(slot),
+                                                Text::new(slot_label(
+                                                    &inventory,
+                                                    &definitions,
+                                                    &localization,
+                                                    slot,
+                                                )),
+                                                TextColor(Color::WHITE),
+                                                TextLayout::new_with_justify(JustifyText::Center),
+                                                TextFont {
+                                                    font: font.clone(),
+                                                    font_size: 28.,
+                                                    ..Default::default()
+                                                },
+                                            ));
+                                        });
+                                }
+                            });
+                    }
+                });
+
+            parent.spawn((
+                InventoryDescriptionText,
+                Text::new(""),
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 28.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+pub fn despawn_inventory_menu(mut commands: Commands, query: Query<Entity, With<InventoryUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn refresh_inventory_menu(
+    inventory: Res<Inventory>,
+    definitions: Res<ItemDefinitions>,
+    localization: Res<Localization>,
+    mut slot_text_query: Query<(&InventorySlotText, &mut Text)>,
+) {
+    if !inventory.is_changed() {
+        return;
+    }
+
+    for (slot_text, mut text) in slot_text_query.iter_mut() {
+        text.0 = slot_label(&inventory, &definitions, &localization, slot_text.0);
+    }
+}
+
+pub fn inventory_slot_handler(
+    button_query: Query<(&Interaction, &InventorySlotButton), Changed<Interaction>>,
+    inventory: Res<Inventory>,
+    definitions: Res<ItemDefinitions>,
+    localization: Res<Localization>,
+    mut description_query: Query<&mut Text, With<InventoryDescriptionText>>,
+    mut use_item_events: EventWriter<UseItemEvent>,
+) {
+    for (interaction, slot_button) in button_query.iter() {
+        match *interaction {
+            Interaction::Pressed => {
+                use_item_events.write(UseItemEvent {
+                    slot: slot_button.0,
+                });
+            }
+            Interaction::Hovered => {
+                let description = match inventory.slots.get(slot_button.0) {
+                    Some(Some(stack)) => definitions
+                        .get(&stack.item)
+                        .map(|definition| {
+                            localization
+                                .text_or(
+                                    &format!("item.{}.description", stack.item),
+                                    &definition.description,
+                                )
+                                .to_string()
+                        })
+                        .unwrap_or_default(),
+                    _ => String::new(),
+                };
+
+                for mut text in description_query.iter_mut() {
+                    text.0 = description.clone();
+                }
+            }
+            Interaction::None => {}
+        }
+    }
+}
+
+pub fn open_inventory_handler(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut next_pause_options_state: ResMut<NextState<PauseOptionsState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Tab) {
+        next_pause_state.set(PauseState::Paused);
+        next_pause_options_state.set(PauseOptionsState::Inventory);
+    }
+}
+
+pub fn close_inventory_handler(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut next_pause_options_state: ResMut<NextState<PauseOptionsState>>,
+) {
+    if keyboard_input.any_just_pressed([KeyCode::Tab, KeyCode::Escape]) {
+        next_pause_state.set(PauseState::InGame);
+        next_pause_options_state.set(PauseOptionsState::None);
+    }
+}
@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 use bevy_light_2d::prelude::*;
-use rand::prelude::*;
 
-use crate::{GameState, MainMenuState, PauseState};
+use crate::{
+    GameState, MainMenuState, PauseState,
+    chapters::{ChapterDefinitions, ChapterProgress, CurrentChapter, FIRST_CHAPTER},
+    lighting::LightFlicker,
+    localization::{Localization, LocalizedText},
+};
 
 const MAIN_MENU_WIDTH: f32 = 1920.;
 
@@ -15,7 +19,22 @@ pub struct MainMenuUI;
 #[derive(Component)]
 pub struct MainMenuLight;
 
-const MAIN_MENU_LIST: [&str; 4] = ["New Game", "Load Game", "Options", "Quit"];
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MainMenuButton {
+    NewGame,
+    LoadGame,
+    Chapters,
+    Options,
+    Quit,
+}
+
+const MAIN_MENU_LIST: [(MainMenuButton, &str); 5] = [
+    (MainMenuButton::NewGame, "main_menu.new_game"),
+    (MainMenuButton::LoadGame, "main_menu.load_game"),
+    (MainMenuButton::Chapters, "main_menu.chapters"),
+    (MainMenuButton::Options, "main_menu.options"),
+    (MainMenuButton::Quit, "main_menu.quit"),
+];
 
 pub fn spawn_main_menu_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
     let background_image = asset_server.load("ui/main_menu/bg.png");
@@ -31,14 +50,15 @@ pub fn spawn_main_menu_scene(mut commands: Commands, asset_server: Res<AssetServ
                     color: Color::srgb(237. / 255., 202. / 225., 148. / 255.),
                     ..Default::default()
                 },
+                LightFlicker::random(2.0, 8.0, 0.08),
                 Transform::from_xyz((MAIN_MENU_WIDTH / 2.) - 415., -90., 500.),
             ));
         });
 }
 
-pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let font = asset_server.load("ui/fonts/pixeloid_mono.ttf");
-    let font_bold = asset_server.load("ui/fonts/pixeloid_mono_bold.ttf");
+pub fn spawn_main_menu(mut commands: Commands, localization: Res<Localization>) {
+    let font = localization.font();
+    let font_bold = localization.font_bold();
 
     commands
         .spawn((
@@ -79,10 +99,10 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 });
         })
         .with_children(|parent| {
-            MAIN_MENU_LIST.iter().for_each(|label| {
+            MAIN_MENU_LIST.iter().for_each(|(button, label)| {
                 parent
                     .spawn((
-                        Name::new(label.to_string()),
+                        *button,
                         Button,
                         Node {
                             width: Val::Px(502.),
@@ -110,7 +130,8 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                             })
                             .with_children(|parent| {
                                 parent.spawn((
-                                    Text::new(label.to_string()),
+                                    LocalizedText(label),
+                                    Text::default(),
                                     TextColor(Color::WHITE),
                                     TextLayout::new_with_justify(JustifyText::Center),
                                     TextFont {
@@ -126,52 +147,51 @@ pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
 }
 
 pub fn button_pressed_handler(
-    button_query: Query<(&Interaction, &Name), Changed<Interaction>>,
+    mut commands: Commands,
+    button_query: Query<(&Interaction, &MainMenuButton), Changed<Interaction>>,
+    definitions: Res<ChapterDefinitions>,
+    progress: Res<ChapterProgress>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut next_main_menu_state: ResMut<NextState<MainMenuState>>,
     mut next_pause_state: ResMut<NextState<PauseState>>,
 ) {
-    for (interaction, name) in button_query.iter() {
+    for (interaction, button) in button_query.iter() {
         if *interaction != Interaction::Pressed {
             continue;
         }
 
-        match name.as_str() {
-            "New Game" => {
+        match button {
+            MainMenuButton::NewGame => {
+                let first_chapter = definitions
+                    .0
+                    .first()
+                    .map(|chapter| chapter.id.as_str())
+                    .unwrap_or(FIRST_CHAPTER);
+
+                commands.insert_resource(CurrentChapter::new(first_chapter));
                 next_game_state.set(GameState::InGame);
                 next_pause_state.set(PauseState::InGame);
                 next_main_menu_state.set(MainMenuState::None);
             }
-            "Load Game" => return,
-            "Options" => next_main_menu_state.set(MainMenuState::Options),
-            "Quit" => std::process::exit(0),
-            _ => return,
-        }
-    }
-}
-
-#[derive(Resource)]
-pub struct MainMenuLightFlickerTimer {
-    pub timer: Timer,
-}
-
-impl Default for MainMenuLightFlickerTimer {
-    fn default() -> Self {
-        MainMenuLightFlickerTimer {
-            timer: Timer::from_seconds(0.08, TimerMode::Repeating),
-        }
-    }
-}
-
-pub fn light_flicker(
-    time: Res<Time>,
-    mut flicker_timer: ResMut<MainMenuLightFlickerTimer>,
-    mut query: Query<&mut PointLight2d, With<MainMenuLight>>,
-) {
-    if flicker_timer.timer.tick(time.delta()).just_finished() {
-        let intensity: f32 = rand::rng().random_range(2.0..8.0);
-        for mut light in query.iter_mut() {
-            light.intensity = intensity;
+            MainMenuButton::LoadGame => {
+                // Continues the last chapter that was played
+                let Some(chapter) = progress
+                    .last_played
+                    .as_deref()
+                    .and_then(|id| definitions.get(id))
+                    .filter(|chapter| progress.is_unlocked(chapter))
+                else {
+                    return;
+                };
+
+                commands.insert_resource(CurrentChapter::new(&chapter.id));
+                next_game_state.set(GameState::InGame);
+                next_pause_state.set(PauseState::InGame);
+                next_main_menu_state.set(MainMenuState::None);
+            }
+            MainMenuButton::Chapters => next_main_menu_state.set(MainMenuState::ChapterSelect),
+            MainMenuButton::Options => next_main_menu_state.set(MainMenuState::Options),
+            MainMenuButton::Quit => std::process::exit(0),
         }
     }
 }
@@ -1,52 +1,297 @@
+pub mod chapter_select;
+pub mod dialogue_box;
 pub mod health_ui;
 pub mod in_game_options_menu;
+pub mod inventory_menu;
 pub mod main_menu;
 pub mod options;
 pub mod paused_menu;
+pub mod quest_log;
+pub mod widgets;
 
-use bevy::{prelude::*, window::WindowMode};
+use bevy::{prelude::*, window::PrimaryWindow};
 
-use crate::{GameOptions, WindowModeSelection};
+use crate::{
+    FRAME_LIMIT_PRESETS, GameOptions, PresentModeSelection, RESOLUTION_PRESETS,
+    WindowModeSelection,
+    localization::{Language, LocalizedText},
+    sounds::VolumeSetting,
+    ui::widgets::{
+        ButtonSize, OptionValue, Slider, SliderSetting, WidgetLabel, spawn_labelled_row,
+        spawn_menu_button, spawn_slider, spawn_toggle_group,
+    },
+};
 
-#[derive(Component)]
-pub struct ScreenModeButton;
+// Every UI size is laid out for this window size and scaled to fit the actual one
+const REFERENCE_WIDTH: f32 = 1920.;
+const REFERENCE_HEIGHT: f32 = 1080.;
+
+// Widths of the widgets on the right of an options row, wide rows take the whole grid
+const WIDE_WIDGET_WIDTH: f32 = 776.;
+const NARROW_WIDGET_WIDTH: f32 = 360.;
 
 #[derive(Component)]
-pub struct MusicVolumeLevel;
+pub struct OptionsBackButton;
 
-pub fn screen_mode_button_handler(
-    button_query: Query<(&Interaction, &Name), (Changed<Interaction>, With<ScreenModeButton>)>,
-    mut game_options: ResMut<GameOptions>,
-    mut windows_query: Query<&mut Window>,
+// Title, every option and a back button, shared by the main menu and pause menu options
+pub fn spawn_options_menu_content(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    font_bold: &Handle<Font>,
+    game_options: &GameOptions,
+    monitor_count: usize,
 ) {
-    for (interaction, name) in button_query.iter() {
-        if *interaction != Interaction::Pressed {
-            continue;
-        }
+    parent.spawn((
+        LocalizedText("options.title"),
+        Text::default(),
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Center),
+        TextFont {
+            font: font_bold.clone(),
+            font_size: 94.,
+            ..Default::default()
+        },
+    ));
 
-        match name.as_str() {
-            "Fullscreen" => {
-                if game_options.window_mode == WindowModeSelection::Fullscreen {
-                    return; // Already in fullscreen mode
-                }
+    parent
+        .spawn(Node {
+            width: Val::Percent(80.),
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            justify_content: JustifyContent::SpaceBetween,
+            row_gap: Val::Px(12.),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            spawn_display_options(parent, font, monitor_count);
+            spawn_general_options(parent, font, game_options);
+            spawn_audio_options(parent, font, game_options);
+        });
 
-                if let Ok(mut window) = windows_query.single_mut() {
-                    window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Primary);
-                    game_options.window_mode = WindowModeSelection::Fullscreen;
-                }
-            }
-            "Windowed" => {
-                if game_options.window_mode == WindowModeSelection::Windowed {
-                    return; // Already in windowed mode
-                }
-
-                if let Ok(mut window) = windows_query.single_mut() {
-                    window.mode = WindowMode::Windowed;
-                    game_options.window_mode = WindowModeSelection::Windowed;
-                }
-            }
-            _ => return,
-        }
+    spawn_menu_button(
+        parent,
+        font,
+        WidgetLabel::Key("options.back"),
+        ButtonSize::LARGE,
+        OptionsBackButton,
+    );
+}
+
+fn wide_row() -> Val {
+    Val::Percent(100.)
+}
+
+fn narrow_row() -> Val {
+    Val::Percent(48.)
+}
+
+// Screen mode, monitor, resolution, vsync and frame cap
+fn spawn_display_options(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    monitor_count: usize,
+) {
+    spawn_labelled_row(parent, font, "options.screen_mode", wide_row(), |parent| {
+        spawn_toggle_group(
+            parent,
+            font,
+            WIDE_WIDGET_WIDTH,
+            vec![
+                (
+                    WidgetLabel::Key("options.fullscreen"),
+                    OptionValue::WindowMode(WindowModeSelection::Fullscreen),
+                ),
+                (
+                    WidgetLabel::Key("options.exclusive_fullscreen"),
+                    OptionValue::WindowMode(WindowModeSelection::ExclusiveFullscreen),
+                ),
+                (
+                    WidgetLabel::Key("options.windowed"),
+                    OptionValue::WindowMode(WindowModeSelection::Windowed),
+                ),
+            ],
+        );
+    });
+
+    spawn_labelled_row(parent, font, "options.resolution", wide_row(), |parent| {
+        spawn_toggle_group(
+            parent,
+            font,
+            WIDE_WIDGET_WIDTH,
+            RESOLUTION_PRESETS
+                .iter()
+                .map(|&(width, height)| {
+                    (
+                        WidgetLabel::Text(format!("{width}x{height}")),
+                        OptionValue::Resolution((width, height)),
+                    )
+                })
+                .collect(),
+        );
+    });
+
+    spawn_labelled_row(parent, font, "options.frame_limit", wide_row(), |parent| {
+        spawn_toggle_group(
+            parent,
+            font,
+            WIDE_WIDGET_WIDTH,
+            FRAME_LIMIT_PRESETS
+                .iter()
+                .map(|&frame_limit| {
+                    let label = match frame_limit {
+                        Some(limit) => WidgetLabel::Text(limit.to_string()),
+                        None => WidgetLabel::Key("options.unlimited"),
+                    };
+                    (label, OptionValue::FrameLimit(frame_limit))
+                })
+                .collect(),
+        );
+    });
+
+    spawn_labelled_row(parent, font, "options.monitor", narrow_row(), |parent| {
+        spawn_toggle_group(
+            parent,
+            font,
+            NARROW_WIDGET_WIDTH,
+            (0..monitor_count.max(1))
+                .map(|monitor| {
+                    (
+                        WidgetLabel::Text((monitor + 1).to_string()),
+                        OptionValue::Monitor(monitor),
+                    )
+                })
+                .collect(),
+        );
+    });
+
+    spawn_labelled_row(parent, font, "options.vsync", narrow_row(), |parent| {
+        spawn_toggle_group(
+            parent,
+            font,
+            NARROW_WIDGET_WIDTH,
+            PresentModeSelection::ALL
+                .iter()
+                .map(|&present_mode| {
+                    (
+                        WidgetLabel::Key(present_mode.label()),
+                        OptionValue::PresentMode(present_mode),
+                    )
+                })
+                .collect(),
+        );
+    });
+}
+
+fn spawn_general_options(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    game_options: &GameOptions,
+) {
+    spawn_labelled_row(
+        parent,
+        font,
+        "options.pixel_perfect",
+        narrow_row(),
+        |parent| {
+            spawn_toggle_group(
+                parent,
+                font,
+                NARROW_WIDGET_WIDTH,
+                vec![
+                    (
+                        WidgetLabel::Key("options.on"),
+                        OptionValue::PixelPerfect(true),
+                    ),
+                    (
+                        WidgetLabel::Key("options.off"),
+                        OptionValue::PixelPerfect(false),
+                    ),
+                ],
+            );
+        },
+    );
+
+    spawn_labelled_row(parent, font, "options.language", narrow_row(), |parent| {
+        spawn_toggle_group(
+            parent,
+            font,
+            NARROW_WIDGET_WIDTH,
+            vec![
+                (
+                    WidgetLabel::Key("options.language_english"),
+                    OptionValue::Language(Language::English),
+                ),
+                // Thai goes back in here once its fonts ship, see data/locales/th.ron
+            ],
+        );
+    });
+
+    spawn_labelled_row(parent, font, "options.ui_scale", narrow_row(), |parent| {
+        spawn_slider(
+            parent,
+            NARROW_WIDGET_WIDTH,
+            SliderSetting::UiScale,
+            game_options,
+        );
+    });
+}
+
+// Mute toggle and a slider per channel
+fn spawn_audio_options(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    game_options: &GameOptions,
+) {
+    spawn_labelled_row(parent, font, "options.mute", narrow_row(), |parent| {
+        spawn_toggle_group(
+            parent,
+            font,
+            NARROW_WIDGET_WIDTH,
+            vec![
+                (WidgetLabel::Key("options.on"), OptionValue::Muted(true)),
+                (WidgetLabel::Key("options.off"), OptionValue::Muted(false)),
+            ],
+        );
+    });
+
+    for setting in VolumeSetting::ALL {
+        spawn_labelled_row(parent, font, setting.label(), narrow_row(), |parent| {
+            spawn_slider(
+                parent,
+                NARROW_WIDGET_WIDTH,
+                SliderSetting::Volume(setting),
+                game_options,
+            );
+        });
+    }
+}
+
+// Fits the reference layout into the window, letterboxed on the shorter side, and applies the
+// player's own scale on top
+pub fn update_ui_scale(
+    game_options: Res<GameOptions>,
+    windows_query: Query<&Window, With<PrimaryWindow>>,
+    slider_query: Query<(&Slider, &Interaction)>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    // The UI scale slider would move away under the cursor while it's being dragged, so the
+    // new scale waits until it's let go
+    let dragging_ui_scale = slider_query.iter().any(|(slider, interaction)| {
+        slider.0 == SliderSetting::UiScale && *interaction == Interaction::Pressed
+    });
+    if dragging_ui_scale {
+        return;
+    }
+
+    let Ok(window) = windows_query.single() else {
+        return;
+    };
+
+    let fit = (window.width() / REFERENCE_WIDTH).min(window.height() / REFERENCE_HEIGHT);
+    let scale = fit * game_options.ui_scale;
+
+    if scale > 0. && (ui_scale.0 - scale).abs() > f32::EPSILON {
+        ui_scale.0 = scale;
     }
 }
 
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{GameState, MainMenuState, PauseOptionsState, PauseState};
+use crate::{
+    GameState, MainMenuState, PauseOptionsState, PauseState,
+    localization::{Localization, LocalizedText},
+};
 
 #[derive(Component)]
 pub struct PausedUI;
@@ -8,11 +11,25 @@ pub struct PausedUI;
 #[derive(Component)]
 pub struct PausedMenu;
 
-const PAUSED_MENU_LIST: [&str; 2] = ["Options", "Return To Main Menu"];
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PausedMenuButton {
+    QuestLog,
+    Options,
+    ReturnToMainMenu,
+}
+
+const PAUSED_MENU_LIST: [(PausedMenuButton, &str); 3] = [
+    (PausedMenuButton::QuestLog, "paused_menu.quest_log"),
+    (PausedMenuButton::Options, "paused_menu.options"),
+    (
+        PausedMenuButton::ReturnToMainMenu,
+        "paused_menu.return_to_main_menu",
+    ),
+];
 
-pub fn spawn_paused_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let font = asset_server.load("ui/fonts/pixeloid_mono.ttf");
-    let font_bold = asset_server.load("ui/fonts/pixeloid_mono_bold.ttf");
+pub fn spawn_paused_menu(mut commands: Commands, localization: Res<Localization>) {
+    let font = localization.font();
+    let font_bold = localization.font_bold();
 
     commands
         .spawn((
@@ -41,7 +58,8 @@ pub fn spawn_paused_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 })
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Paused"),
+                        LocalizedText("paused_menu.title"),
+                        Text::default(),
                         TextColor(Color::WHITE),
                         TextLayout::new_with_justify(JustifyText::Center),
                         TextFont {
@@ -53,11 +71,11 @@ pub fn spawn_paused_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 });
         })
         .with_children(|parent| {
-            PAUSED_MENU_LIST.iter().for_each(|label| {
+            PAUSED_MENU_LIST.iter().for_each(|(button, label)| {
                 parent
                     .spawn((
                         PausedMenu,
-                        Name::new(label.to_string()),
+                        *button,
                         Button,
                         Node {
                             width: Val::Px(648.),
@@ -85,7 +103,8 @@ pub fn spawn_paused_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                             })
                             .with_children(|parent| {
                                 parent.spawn((
-                                    Text::new(label.to_string()),
+                                    LocalizedText(label),
+                                    Text::default(),
                                     TextColor(Color::WHITE),
                                     TextLayout::new_with_justify(JustifyText::Center),
                                     TextFont {
@@ -101,28 +120,30 @@ pub fn spawn_paused_menu(mut commands: Commands, asset_server: Res<AssetServer>)
 }
 
 pub fn button_pressed_handler(
-    button_query: Query<(&Interaction, &Name), Changed<Interaction>>,
+    button_query: Query<(&Interaction, &PausedMenuButton), Changed<Interaction>>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut next_pause_state: ResMut<NextState<PauseState>>,
     mut next_main_menu_state: ResMut<NextState<MainMenuState>>,
     mut next_pause_options_state: ResMut<NextState<PauseOptionsState>>,
 ) {
-    for (interaction, name) in button_query.iter() {
+    for (interaction, button) in button_query.iter() {
         if *interaction != Interaction::Pressed {
             continue;
         }
 
-        match name.as_str() {
-            "Options" => {
+        match button {
+            PausedMenuButton::QuestLog => {
+                next_pause_options_state.set(PauseOptionsState::QuestLog);
+            }
+            PausedMenuButton::Options => {
                 next_pause_options_state.set(PauseOptionsState::Options);
             }
-            "Return To Main Menu" => {
+            PausedMenuButton::ReturnToMainMenu => {
                 next_pause_state.set(PauseState::None);
                 next_pause_options_state.set(PauseOptionsState::None);
                 next_game_state.set(GameState::MainMenu);
                 next_main_menu_state.set(MainMenuState::MainMenu);
             }
-            _ => return,
         }
     }
 }
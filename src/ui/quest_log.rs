@@ -0,0 +1,223 @@
+use bevy::prelude::*;
+
+use crate::{
+    PauseOptionsState,
+    localization::{Localization, LocalizedText},
+    objectives::{ObjectiveDefinition, ObjectiveDefinitions, ObjectiveLog},
+};
+
+#[derive(Component)]
+pub struct ObjectiveTracker;
+
+#[derive(Component)]
+pub struct ObjectiveTrackerText;
+
+#[derive(Component)]
+pub struct QuestLogUI;
+
+fn objective_title(localization: &Localization, objective: &ObjectiveDefinition) -> String {
+    let title = localization.text_or(
+        &format!("objective.{}.title", objective.id),
+        &objective.title,
+    );
+
+    if objective.optional {
+        localization.format("quest_log.optional", &[("title", title)])
+    } else {
+        title.to_string()
+    }
+}
+
+// Sits to the right of the health bar
+pub fn spawn_objective_tracker(mut commands: Commands, localization: Res<Localization>) {
+    let font = localization.font();
+    let font_bold = localization.font_bold();
+
+    commands
+        .spawn((
+            ObjectiveTracker,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(20.0),
+                left: Val::Px(440.0),
+                max_width: Val::Px(480.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText("quest_log.tracker_title"),
+                Text::default(),
+                TextColor(Color::WHITE),
+                TextFont {
+                    font: font_bold.clone(),
+                    font_size: 20.,
+                    ..Default::default()
+                },
+            ));
+
+            parent.spawn((
+                ObjectiveTrackerText,
+                Text::new(""),
+                TextColor(Color::WHITE.with_alpha(0.85)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 18.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+pub fn despawn_objective_tracker(
+    mut commands: Commands,
+    tracker_query: Query<Entity, With<ObjectiveTracker>>,
+) {
+    for entity in tracker_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn update_objective_tracker(
+    objective_log: Res<ObjectiveLog>,
+    definitions: Res<ObjectiveDefinitions>,
+    localization: Res<Localization>,
+    mut text_query: Query<&mut Text, With<ObjectiveTrackerText>>,
+    tracker_text_added: Query<(), Added<ObjectiveTrackerText>>,
+) {
+    if !objective_log.is_changed() && !localization.is_changed() && tracker_text_added.is_empty() {
+        return;
+    }
+
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    text.0 = objective_log
+        .active(&definitions)
+        .map(|objective| format!("- {}", objective_title(&localization, objective)))
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+pub fn spawn_quest_log(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    objective_log: Res<ObjectiveLog>,
+    definitions: Res<ObjectiveDefinitions>,
+) {
+    let font = localization.font();
+    let font_bold = localization.font_bold();
+
+    commands
+        .spawn((
+            QuestLogUI,
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceEvenly,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText("quest_log.title"),
+                Text::default(),
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextFont {
+                    font: font_bold.clone(),
+                    font_size: 94.,
+                    ..Default::default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    width: Val::Percent(60.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(24.),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    // Locked objectives stay hidden so they don't spoil what's coming
+                    for objective in definitions
+                        .chapter(&objective_log.chapter)
+                        .iter()
+                        .filter(|objective| objective_log.is_unlocked(objective))
+                    {
+                        let completed = objective_log.is_completed(&objective.id);
+                        let color = if completed {
+                            Color::WHITE.with_alpha(0.4)
+                        } else {
+                            Color::WHITE
+                        };
+
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(6.),
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                let mark = if completed { "[x]" } else { "[ ]" };
+                                let title = objective_title(&localization, objective);
+
+                                parent.spawn((
+                                    Text::new(format!("{mark} {title}")),
+                                    TextColor(color),
+                                    TextFont {
+                                        font: font_bold.clone(),
+                                        font_size: 36.,
+                                        ..Default::default()
+                                    },
+                                ));
+
+                                parent.spawn((
+                                    Text::new(localization.text_or(
+                                        &format!("objective.{}.description", objective.id),
+                                        &objective.description,
+                                    )),
+                                    TextColor(color),
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 24.,
+                                        ..Default::default()
+                                    },
+                                ));
+                            });
+                    }
+                });
+
+            parent.spawn((
+                LocalizedText("quest_log.back_hint"),
+                Text::default(),
+                TextColor(Color::WHITE.with_alpha(0.6)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 24.,
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+pub fn despawn_quest_log(mut commands: Commands, query: Query<Entity, With<QuestLogUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn back_to_paused_menu_handler(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<PauseOptionsState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(PauseOptionsState::Paused);
+    }
+}
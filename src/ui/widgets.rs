@@ -0,0 +1,544 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    GameOptions, PresentModeSelection, UI_SCALE_RANGE, WindowModeSelection,
+    localization::{Language, LocalizedText},
+    sounds::VolumeSetting,
+};
+
+const WIDGET_GAP: f32 = 8.;
+const SMALL_BUTTON_HEIGHT: f32 = 48.;
+const LABEL_FONT_SIZE: f32 = 28.;
+const SELECTED_COLOR: Color = Color::srgb(163. / 255., 220. / 255., 154. / 255.);
+// Greyed out while a slider doesn't do anything, e.g. volumes while muted
+const DISABLED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+// Drags snap to this so a slow drag doesn't change the options every frame
+const SLIDER_DRAG_PRECISION: f64 = 0.01;
+
+#[derive(Debug, Clone)]
+pub enum WidgetLabel {
+    // Looked up in the string tables
+    Key(&'static str),
+    // Shown as is, e.g. a resolution
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ButtonSize {
+    pub width: f32,
+    pub height: f32,
+    pub font_size: f32,
+}
+
+impl ButtonSize {
+    pub const LARGE: ButtonSize = ButtonSize {
+        width: 502.,
+        height: 88.,
+        font_size: 48.,
+    };
+
+    pub fn small(width: f32) -> Self {
+        Self {
+            width,
+            height: SMALL_BUTTON_HEIGHT,
+            font_size: LABEL_FONT_SIZE,
+        }
+    }
+}
+
+// An option a toggle group can pick, one variant per setting in `GameOptions`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionValue {
+    WindowMode(WindowModeSelection),
+    Monitor(usize),
+    Resolution((u32, u32)),
+    PresentMode(PresentModeSelection),
+    FrameLimit(Option<u32>),
+    PixelPerfect(bool),
+    Language(Language),
+    Muted(bool),
+}
+
+impl OptionValue {
+    pub fn is_selected(&self, game_options: &GameOptions) -> bool {
+        match *self {
+            OptionValue::WindowMode(window_mode) => game_options.window_mode == window_mode,
+            OptionValue::Monitor(monitor) => game_options.monitor == monitor,
+            OptionValue::Resolution(resolution) => {
+                game_options.window_mode == WindowModeSelection::Windowed
+                    && game_options.resolution == resolution
+            }
+            OptionValue::PresentMode(present_mode) => game_options.present_mode == present_mode,
+            OptionValue::FrameLimit(frame_limit) => game_options.frame_limit == frame_limit,
+            OptionValue::PixelPerfect(pixel_perfect) => game_options.pixel_perfect == pixel_perfect,
+            OptionValue::Language(language) => game_options.language == language,
+            OptionValue::Muted(muted) => game_options.muted == muted,
+        }
+    }
+
+    pub fn apply(&self, game_options: &mut GameOptions) {
+        match *self {
+            OptionValue::WindowMode(window_mode) => game_options.window_mode = window_mode,
+            OptionValue::Monitor(monitor) => game_options.monitor = monitor,
+            // Picking a resolution only makes sense for a window, so it switches to windowed too
+            OptionValue::Resolution(resolution) => {
+                game_options.resolution = resolution;
+                game_options.window_mode = WindowModeSelection::Windowed;
+            }
+            OptionValue::PresentMode(present_mode) => game_options.present_mode = present_mode,
+            OptionValue::FrameLimit(frame_limit) => game_options.frame_limit = frame_limit,
+            OptionValue::PixelPerfect(pixel_perfect) => game_options.pixel_perfect = pixel_perfect,
+            OptionValue::Language(language) => game_options.language = language,
+            OptionValue::Muted(muted) => game_options.muted = muted,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct ToggleButton(pub OptionValue);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliderSetting {
+    Volume(VolumeSetting),
+    UiScale,
+}
+
+impl SliderSetting {
+    // Where the slider sits between 0.0 and 1.0
+    pub fn value(&self, game_options: &GameOptions) -> f64 {
+        match *self {
+            SliderSetting::Volume(setting) => game_options.volume(setting),
+            SliderSetting::UiScale => {
+                let (min, max) = UI_SCALE_RANGE;
+                ((game_options.ui_scale - min) / (max - min)) as f64
+            }
+        }
+    }
+
+    pub fn set_value(&self, game_options: &mut GameOptions, value: f64) {
+        match *self {
+            SliderSetting::Volume(setting) => *game_options.volume_mut(setting) = value,
+            SliderSetting::UiScale => {
+                let (min, max) = UI_SCALE_RANGE;
+                game_options.ui_scale = min + (max - min) * value as f32;
+            }
+        }
+    }
+
+    // How far one arrow key press moves the slider
+    pub fn step(&self) -> f64 {
+        match self {
+            SliderSetting::Volume(_) => 0.05,
+            SliderSetting::UiScale => 0.1,
+        }
+    }
+
+    pub fn disabled(&self, game_options: &GameOptions) -> bool {
+        match self {
+            SliderSetting::Volume(_) => game_options.muted,
+            SliderSetting::UiScale => false,
+        }
+    }
+
+    // Returns the clamped value only when it differs, so callers holding a `ResMut` don't mark
+    // the options changed (and saved) every frame a slider is held still
+    fn changed_value(&self, game_options: &GameOptions, value: f64) -> Option<f64> {
+        let value = value.clamp(0.0, 1.0);
+        ((value - self.value(game_options)).abs() > f64::EPSILON).then_some(value)
+    }
+}
+
+// The track of a slider, clicking or dragging anywhere on it sets the value
+#[derive(Component, Debug)]
+pub struct Slider(pub SliderSetting);
+
+#[derive(Component, Debug)]
+pub struct SliderFill(pub SliderSetting);
+
+// The slider the arrow keys adjust, the last one hovered or dragged
+#[derive(Resource, Default, Debug)]
+pub struct FocusedSlider(pub Option<Entity>);
+
+fn spawn_label(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: WidgetLabel,
+    font_size: f32,
+) {
+    let mut text = parent.spawn((
+        Text::default(),
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(JustifyText::Center),
+        TextFont {
+            font: font.clone(),
+            font_size,
+            ..Default::default()
+        },
+    ));
+
+    match label {
+        WidgetLabel::Key(key) => {
+            text.insert(LocalizedText(key));
+        }
+        WidgetLabel::Text(value) => {
+            text.insert(Text::new(value));
+        }
+    }
+}
+
+pub fn spawn_menu_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: WidgetLabel,
+    size: ButtonSize,
+    bundle: impl Bundle,
+) {
+    parent
+        .spawn((
+            Button,
+            bundle,
+            Node {
+                width: Val::Px(size.width),
+                height: Val::Px(size.height),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(2.)),
+                ..Default::default()
+            },
+            BorderColor(Color::WHITE),
+            BackgroundColor(Color::WHITE.with_alpha(0.0)),
+        ))
+        .with_children(|parent| {
+            spawn_label(parent, font, label, size.font_size);
+        });
+}
+
+// Buttons side by side that share the width, the selected one gets highlighted
+pub fn spawn_toggle_group(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    width: f32,
+    options: Vec<(WidgetLabel, OptionValue)>,
+) {
+    let count = options.len().max(1) as f32;
+    let button_width = (width - WIDGET_GAP * (count - 1.)) / count;
+
+    parent
+        .spawn(Node {
+            width: Val::Px(width),
+            column_gap: Val::Px(WIDGET_GAP),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for (label, value) in options {
+                spawn_menu_button(
+                    parent,
+                    font,
+                    label,
+                    ButtonSize::small(button_width),
+                    ToggleButton(value),
+                );
+            }
+        });
+}
+
+pub fn spawn_slider(
+    parent: &mut ChildSpawnerCommands,
+    width: f32,
+    setting: SliderSetting,
+    game_options: &GameOptions,
+) {
+    parent
+        .spawn((
+            Button,
+            Slider(setting),
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(SMALL_BUTTON_HEIGHT),
+                border: UiRect::all(Val::Px(2.)),
+                ..Default::default()
+            },
+            BorderColor(Color::WHITE),
+            BackgroundColor(Color::WHITE.with_alpha(0.0)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                SliderFill(setting),
+                Node {
+                    width: Val::Percent(setting.value(game_options) as f32 * 100.),
+                    height: Val::Percent(100.),
+                    ..Default::default()
+                },
+                BackgroundColor(slider_fill_color(setting.disabled(game_options))),
+            ));
+        });
+}
+
+// A label on the left and whatever widget `content` spawns on the right
+pub fn spawn_labelled_row(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: &'static str,
+    width: Val,
+    content: impl FnOnce(&mut ChildSpawnerCommands),
+) {
+    parent
+        .spawn(Node {
+            width,
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(WIDGET_GAP * 2.),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            spawn_label(parent, font, WidgetLabel::Key(label), LABEL_FONT_SIZE);
+            content(parent);
+        });
+}
+
+fn slider_fill_color(disabled: bool) -> Color {
+    if disabled {
+        DISABLED_COLOR
+    } else {
+        SELECTED_COLOR
+    }
+}
+
+pub fn toggle_button_handler(
+    button_query: Query<(&Interaction, &ToggleButton), Changed<Interaction>>,
+    mut game_options: ResMut<GameOptions>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        // Only touch the resource on an actual change, a lot of systems resync on it
+        if !button.0.is_selected(&game_options) {
+            button.0.apply(&mut game_options);
+        }
+    }
+}
+
+pub fn update_toggle_buttons(
+    game_options: Res<GameOptions>,
+    mut button_query: Query<(Ref<ToggleButton>, &mut BorderColor)>,
+) {
+    for (button, mut border_color) in button_query.iter_mut() {
+        if !game_options.is_changed() && !button.is_added() {
+            continue;
+        }
+
+        border_color.0 = if button.0.is_selected(&game_options) {
+            SELECTED_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+pub fn drag_sliders(
+    slider_query: Query<(
+        Entity,
+        &Slider,
+        &Interaction,
+        &ComputedNode,
+        &GlobalTransform,
+    )>,
+    windows_query: Query<&Window, With<PrimaryWindow>>,
+    mut game_options: ResMut<GameOptions>,
+    mut focused_slider: ResMut<FocusedSlider>,
+) {
+    let cursor_position = windows_query
+        .single()
+        .ok()
+        .and_then(|window| window.physical_cursor_position());
+
+    for (entity, slider, interaction, computed_node, global_transform) in slider_query.iter() {
+        if *interaction == Interaction::None {
+            continue;
+        }
+
+        if focused_slider.0 != Some(entity) {
+            focused_slider.0 = Some(entity);
+        }
+
+        let Some(cursor_position) = cursor_position else {
+            continue;
+        };
+
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        // Node sizes and transforms are in physical pixels, same as the cursor
+        let size = computed_node.size();
+        if size.x <= 0. {
+            continue;
+        }
+
+        let left = global_transform.translation().x - size.x / 2.;
+        let value = ((cursor_position.x - left) / size.x) as f64;
+        let value = (value / SLIDER_DRAG_PRECISION).round() * SLIDER_DRAG_PRECISION;
+
+        if let Some(value) = slider.0.changed_value(&game_options, value) {
+            slider.0.set_value(&mut game_options, value);
+        }
+    }
+}
+
+pub fn adjust_focused_slider(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    focused_slider: Res<FocusedSlider>,
+    slider_query: Query<&Slider>,
+    mut game_options: ResMut<GameOptions>,
+) {
+    let direction = if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        -1.0
+    } else if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        1.0
+    } else {
+        return;
+    };
+
+    let Some(slider) = focused_slider
+        .0
+        .and_then(|entity| slider_query.get(entity).ok())
+    else {
+        return;
+    };
+
+    let value = slider.0.value(&game_options) + slider.0.step() * direction;
+    if let Some(value) = slider.0.changed_value(&game_options, value) {
+        slider.0.set_value(&mut game_options, value);
+    }
+}
+
+pub fn update_sliders(
+    game_options: Res<GameOptions>,
+    focused_slider: Res<FocusedSlider>,
+    mut fill_query: Query<(Ref<SliderFill>, &mut Node, &mut BackgroundColor)>,
+    mut slider_query: Query<(Entity, &mut BorderColor), With<Slider>>,
+) {
+    for (fill, mut node, mut color) in fill_query.iter_mut() {
+        if !game_options.is_changed() && !fill.is_added() {
+            continue;
+        }
+
+        node.width = Val::Percent(fill.0.value(&game_options) as f32 * 100.);
+        color.0 = slider_fill_color(fill.0.disabled(&game_options));
+    }
+
+    if !focused_slider.is_changed() {
+        return;
+    }
+
+    for (entity, mut border_color) in slider_query.iter_mut() {
+        border_color.0 = if focused_slider.0 == Some(entity) {
+            SELECTED_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volume_slider_reads_and_writes_its_channel() {
+        let mut game_options = GameOptions::default();
+        let slider = SliderSetting::Volume(VolumeSetting::Music);
+
+        slider.set_value(&mut game_options, 0.25);
+
+        assert_eq!(slider.value(&game_options), 0.25);
+        assert_eq!(game_options.volume(VolumeSetting::Music), 0.25);
+        assert_eq!(game_options.volume(VolumeSetting::Sfx), 1.0);
+    }
+
+    #[test]
+    fn slider_changes_are_clamped() {
+        let game_options = GameOptions::default();
+        let slider = SliderSetting::Volume(VolumeSetting::Sfx);
+
+        assert_eq!(slider.changed_value(&game_options, -0.2), Some(0.0));
+        assert_eq!(slider.changed_value(&game_options, 0.5), Some(0.5));
+    }
+
+    #[test]
+    fn unchanged_slider_values_are_skipped() {
+        let game_options = GameOptions::default();
+        let slider = SliderSetting::Volume(VolumeSetting::Sfx);
+
+        assert_eq!(slider.changed_value(&game_options, 1.0), None);
+        assert_eq!(slider.changed_value(&game_options, 1.4), None);
+    }
+
+    #[test]
+    fn ui_scale_slider_maps_onto_the_scale_range() {
+        let mut game_options = GameOptions::default();
+        let slider = SliderSetting::UiScale;
+        assert_eq!(slider.value(&game_options), 0.5);
+
+        slider.set_value(&mut game_options, 0.0);
+        assert_eq!(game_options.ui_scale, UI_SCALE_RANGE.0);
+
+        slider.set_value(&mut game_options, 1.0);
+        assert_eq!(game_options.ui_scale, UI_SCALE_RANGE.1);
+        assert_eq!(slider.value(&game_options), 1.0);
+
+        // Muting has nothing to do with the UI scale
+        game_options.muted = true;
+        assert!(!slider.disabled(&game_options));
+    }
+
+    #[test]
+    fn volume_sliders_are_disabled_while_muted() {
+        let mut game_options = GameOptions::default();
+        let slider = SliderSetting::Volume(VolumeSetting::Master);
+        assert!(!slider.disabled(&game_options));
+
+        OptionValue::Muted(true).apply(&mut game_options);
+        assert!(slider.disabled(&game_options));
+    }
+
+    #[test]
+    fn applied_options_are_selected() {
+        let mut game_options = GameOptions::default();
+        let options = [
+            OptionValue::WindowMode(WindowModeSelection::ExclusiveFullscreen),
+            OptionValue::Monitor(1),
+            OptionValue::PresentMode(PresentModeSelection::Off),
+            OptionValue::FrameLimit(Some(60)),
+            OptionValue::PixelPerfect(true),
+            OptionValue::Language(Language::Thai),
+            OptionValue::Muted(true),
+        ];
+
+        for option in options {
+            assert!(!option.is_selected(&game_options));
+            option.apply(&mut game_options);
+            assert!(option.is_selected(&game_options));
+        }
+    }
+
+    #[test]
+    fn picking_a_resolution_switches_to_windowed() {
+        let mut game_options = GameOptions::default();
+        let resolution = OptionValue::Resolution((1920, 1080));
+        assert!(!resolution.is_selected(&game_options));
+
+        resolution.apply(&mut game_options);
+
+        assert_eq!(game_options.resolution, (1920, 1080));
+        assert_eq!(game_options.window_mode, WindowModeSelection::Windowed);
+        assert!(resolution.is_selected(&game_options));
+
+        // The resolution only counts as picked while windowed
+        OptionValue::WindowMode(WindowModeSelection::Fullscreen).apply(&mut game_options);
+        assert!(!resolution.is_selected(&game_options));
+    }
+}